@@ -0,0 +1,107 @@
+use std::fmt::{Display, Formatter};
+use crate::calculator::{missing_operand, missing_operator};
+use crate::collections::{Queue, Stack};
+use crate::error::{Error, Span};
+use crate::token::*;
+use crate::value::Value;
+
+/// Expression tree, built from the output queue of the shunting yard.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Expr {
+	Literal(Value),
+	Variable(String),
+	Unary(UnaryOperatorType, Box<Expr>),
+	Binary(BinaryOperatorType, Box<Expr>, Box<Expr>),
+	Call(String, Vec<Expr>),
+	/// Assignment to a global variable, evaluates to the assigned value.
+	/// Compound assignments like `x += 1` carry their operator.
+	Assign(String, Option<BinaryOperatorType>, Box<Expr>)
+}
+
+impl Expr {
+	/// Builds the tree from tokens in reverse polish notation.
+	pub fn from_rpn(mut value_queue: Queue<SpannedToken>, src: &str) -> Result<Expr, Error> {
+		let mut expr_stack: Stack<Expr> = Stack::new();
+
+		while let Some(spanned) = value_queue.dequeue() {
+			match &spanned.token {
+				Token::Integer(int) => {
+					expr_stack.push(Expr::Literal(Value::Integer(*int)));
+				},
+				Token::BigInteger(big) => {
+					expr_stack.push(Expr::Literal(Value::BigInteger(big.clone())));
+				},
+				Token::Float(flt) => {
+					expr_stack.push(Expr::Literal(Value::Float(*flt)));
+				},
+				Token::Identifier(symbol) => {
+					expr_stack.push(Expr::Variable(symbol.clone()));
+				},
+				Token::UnaryOperator(op) => {
+					let Some(operand) = expr_stack.pop() else {
+						return Err(missing_operand(&spanned.token, spanned.span));
+					};
+					expr_stack.push(Expr::Unary(*op, Box::new(operand)));
+				},
+				Token::BinaryOperator(op) => {
+					if expr_stack.len() < 2 {
+						return Err(missing_operand(&spanned.token, spanned.span));
+					}
+					let right = expr_stack.pop().unwrap();
+					let left = expr_stack.pop().unwrap();
+					expr_stack.push(Expr::Binary(*op, Box::new(left), Box::new(right)));
+				},
+				Token::Call(name, argc) => {
+					if expr_stack.len() < *argc {
+						return Err(missing_operand(&spanned.token, spanned.span));
+					}
+					let mut args = Vec::with_capacity(*argc);
+					for _ in 0..*argc {
+						args.push(expr_stack.pop().unwrap());
+					}
+					args.reverse();
+					expr_stack.push(Expr::Call(name.clone(), args));
+				},
+				Token::Store(name, op) => {
+					let Some(value) = expr_stack.pop() else {
+						return Err(missing_operand(&spanned.token, spanned.span));
+					};
+					expr_stack.push(Expr::Assign(name.clone(), *op, Box::new(value)));
+				},
+				_ => { return Err(Error::Parse(format!("Unexpected token \"{:?}\"", spanned.token), spanned.span)); }
+			}
+		}
+
+		let result = expr_stack.pop();
+		if expr_stack.pop().is_some() {
+			return Err(missing_operator(src));
+		}
+		result.ok_or_else(|| Error::Parse("No value in result stack!".to_string(), Span::new(0, src.chars().count())))
+	}
+}
+
+/// Prints the expression back as source, with every operation in parentheses.
+impl Display for Expr {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			// Debug keeps the decimal point, so the output parses back as a float
+			Expr::Literal(Value::Float(flt)) => write!(f, "{:?}", flt),
+			Expr::Literal(value) => write!(f, "{}", value),
+			Expr::Variable(name) => write!(f, "{}", name),
+			Expr::Unary(op, operand) => write!(f, "({}{})", op.symbol(), operand),
+			Expr::Binary(op, left, right) => write!(f, "({} {} {})", left, op.symbol(), right),
+			Expr::Call(name, args) => {
+				write!(f, "{}(", name)?;
+				for (i, arg) in args.iter().enumerate() {
+					if i > 0 {
+						write!(f, ", ")?;
+					}
+					write!(f, "{}", arg)?;
+				}
+				write!(f, ")")
+			},
+			Expr::Assign(name, None, value) => write!(f, "({} = {})", name, value),
+			Expr::Assign(name, Some(op), value) => write!(f, "({} {}= {})", name, op.symbol(), value)
+		}
+	}
+}
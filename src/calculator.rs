@@ -0,0 +1,592 @@
+use std::collections::HashMap;
+use crate::ast::Expr;
+use crate::collections::{Queue, Stack};
+use crate::error::{Error, Span};
+use crate::functions::{find_builtin, UserFunction};
+use crate::token::*;
+use crate::trace::{TraceEvent, Tracer};
+use crate::value::Value;
+use crate::value::Value::{Float, Integer};
+use crate::word::WordSize;
+
+/// Calls nested deeper than this fail instead of overflowing the stack.
+pub const MAX_CALL_DEPTH: usize = 256;
+/// Expression trees, including the bodies of the functions called, are evaluated this deep at most.
+pub const MAX_EVAL_DEPTH: usize = 512;
+
+/// Fails to compile if `Calculator` can no longer be moved to another thread.
+fn _assert_send<T: Send>() {}
+const _: fn() = _assert_send::<Calculator>;
+
+pub struct Calculator {
+	globals: HashMap<String, Value>,
+	functions: HashMap<String, UserFunction>,
+	/// Arguments of the user-defined functions currently being called, innermost last.
+	scopes: Vec<HashMap<String, Value>>,
+	/// Nodes of expression trees currently being evaluated, innermost last.
+	depth: usize,
+	/// Programmer mode, integers wrap around at this width if set.
+	word_size: Option<WordSize>,
+	tracer: Option<Tracer>,
+	/// Results of earlier lines, read back as `ans`, `_` or `$1`, `$2`, ...
+	results: Vec<Value>
+}
+impl Calculator {
+	pub fn new() -> Self {
+		Self {
+			globals: HashMap::new(),
+			functions: HashMap::new(),
+			scopes: Vec::new(),
+			depth: 0,
+			word_size: None,
+			tracer: None,
+			results: Vec::new()
+		}
+	}
+	pub fn word_size(&self) -> Option<WordSize> {
+		self.word_size
+	}
+	/// Enables programmer mode with a fixed integer width, or disables it with `None`.
+	pub fn set_word_size(&mut self, word_size: Option<WordSize>) {
+		self.word_size = word_size;
+	}
+	/// Reports every step of sorting and evaluating a statement to `tracer`, or stops tracing with `None`.
+	/// Returns the tracer that was set before.
+	pub fn set_tracer(&mut self, tracer: Option<Tracer>) -> Option<Tracer> {
+		std::mem::replace(&mut self.tracer, tracer)
+	}
+	pub fn is_tracing(&self) -> bool {
+		self.tracer.is_some()
+	}
+	pub fn set_var(&mut self, name: &String, value: &Value) {
+		if !self.globals.contains_key(name) {
+			self.globals.insert(name.clone(), value.clone());
+		} else {
+			*self.globals.get_mut(name).unwrap() = value.clone();
+		}
+	}
+	pub fn globals(&self) -> &HashMap<String, Value> {
+		&self.globals
+	}
+	/// Deletes a global variable, returning its value if it was set.
+	pub fn remove_var(&mut self, name: &str) -> Option<Value> {
+		self.globals.remove(name)
+	}
+	/// Looks up a variable, arguments of the function being called shadow globals.
+	/// Names of results pushed with `push_result` are looked up last, so variables named `ans` or `_` hide them.
+	pub fn get_var(&self, name: &str) -> Option<&Value> {
+		self.scopes.last().and_then(|scope| scope.get(name))
+			.or_else(|| self.globals.get(name))
+			.or_else(|| self.get_result(name))
+	}
+	/// Numbers the result of a line, starting at 1, and makes it the value of `ans` and `_`.
+	/// Results are only numbered when pushed here, `calculate` does not keep them.
+	pub fn push_result(&mut self, value: Value) -> usize {
+		self.results.push(value);
+		self.results.len()
+	}
+	pub fn results(&self) -> &[Value] {
+		&self.results
+	}
+	pub fn define_function(&mut self, name: &str, params: Vec<String>, body: Expr) -> Result<(), Error> {
+		if find_builtin(name).is_some() {
+			return Err(Error::Parse(format!("Function \"{}\" is built in and can't be redefined", name), Span::default()));
+		}
+		for (i, param) in params.iter().enumerate() {
+			if params[..i].contains(param) {
+				return Err(Error::Parse(format!("Parameter \"{}\" is declared twice", param), Span::default()));
+			}
+		}
+		self.functions.insert(name.to_string(), UserFunction { params, body });
+		Ok(())
+	}
+	pub fn get_function(&self, name: &str) -> Option<&UserFunction> {
+		self.functions.get(name)
+	}
+	/// User-defined functions by name, built-ins are listed in `functions::BUILTINS`.
+	pub fn functions(&self) -> &HashMap<String, UserFunction> {
+		&self.functions
+	}
+	pub fn remove_function(&mut self, name: &str) -> Option<UserFunction> {
+		self.functions.remove(name)
+	}
+	/// Writes the global variables and user-defined functions as statements, one per line, that read back to the same state.
+	/// Floats keep their decimal point so they are read back as floats, undefined variables are left out.
+	pub fn export(&self) -> String {
+		let mut globals: Vec<_> = self.globals.iter().filter(|(_, value)| !value.is_undefined()).collect();
+		globals.sort_by(|a, b| a.0.cmp(b.0));
+		let mut functions: Vec<_> = self.functions.iter().collect();
+		functions.sort_by(|a, b| a.0.cmp(b.0));
+
+		let mut src = String::new();
+		for (name, value) in globals {
+			src.push_str(&format!("{} = {}\n", name, Expr::Literal(value.clone())));
+		}
+		for (name, function) in functions {
+			src.push_str(&format!("def {}({}) = {}\n", name, function.params.join(", "), function.body));
+		}
+		src
+	}
+	/// Deletes every global variable and user-defined function, results and settings are kept.
+	pub fn clear(&mut self) {
+		self.globals.clear();
+		self.functions.clear();
+	}
+	/// Tokenizes `src` and evaluates its statements, returning the value of the last one.
+	pub fn calculate(&mut self, src: &str) -> Result<Value, Error> {
+		let mut values = self.calculate_all(src)?;
+		Ok(values.pop().unwrap())
+	}
+	/// Evaluates the `;` separated statements of `src` in order and returns the value of each.
+	/// Empty statements are skipped, a statement starting with `def` defines a function and evaluates to `Undefined`.
+	pub fn calculate_all(&mut self, src: &str) -> Result<Vec<Value>, Error> {
+		let tokens = Token::tokenize(src)?;
+		let mut values = Vec::new();
+		for statement in tokens.split(|spanned| spanned.token == Token::Semicolon) {
+			if statement.is_empty() {
+				continue;
+			}
+			if let Some(SpannedToken { token: Token::Identifier(keyword), .. }) = statement.first() {
+				if keyword == "def" {
+					self.define(&statement[1..], src)?;
+					values.push(Value::Undefined);
+					continue;
+				}
+			}
+			let value_queue = self.sort(statement, src)?;
+			values.push(self.execute(value_queue, src)?);
+		}
+		if values.is_empty() {
+			return Err(Error::Parse("No value in result stack!".to_string(), Span::new(0, src.chars().count())));
+		}
+		Ok(values)
+	}
+	/// Parses `src` into an expression tree without evaluating it.
+	pub fn parse(&self, src: &str) -> Result<Expr, Error> {
+		let tokens = Token::tokenize(src)?;
+		let value_queue = self.sort(&tokens, src)?;
+		Expr::from_rpn(value_queue, src)
+	}
+	/// Evaluates an expression tree.
+	/// The tree holds no source positions, so errors raised here carry an empty span.
+	pub fn evaluate_expr(&mut self, expr: &Expr) -> Result<Value, Error> {
+		// Every level takes room on the stack, not only calls, the function name is filled in by `call_function`
+		if self.depth >= MAX_EVAL_DEPTH {
+			return Err(Error::Recursion(String::new(), Span::default()));
+		}
+		self.depth += 1;
+		let result = self.evaluate_node(expr);
+		self.depth -= 1;
+		result
+	}
+	fn evaluate_node(&mut self, expr: &Expr) -> Result<Value, Error> {
+		match expr {
+			Expr::Literal(value) => Ok(self.wrap(value.clone())),
+			Expr::Variable(name) => {
+				match self.get_var(name) {
+					Some(var) => Ok(self.wrap(var.clone())),
+					None => Err(Error::UndefinedVariable(name.clone(), Span::default()))
+				}
+			},
+			Expr::Unary(op, operand) => {
+				let value = self.evaluate_expr(operand)?;
+				apply_unary(*op, value, Span::default()).map(|value| self.wrap(value))
+			},
+			Expr::Binary(op, left, right) => {
+				let left = self.evaluate_expr(left)?;
+				let right = self.evaluate_expr(right)?;
+				apply_binary(*op, left, right, Span::default()).map(|value| self.wrap(value))
+			},
+			Expr::Call(name, args) => {
+				let mut values = Vec::with_capacity(args.len());
+				for arg in args {
+					values.push(self.evaluate_expr(arg)?);
+				}
+				let result = self.call_function(name, &values, Span::default())?;
+				Ok(self.wrap(result))
+			},
+			Expr::Assign(name, op, value) => {
+				let value = self.evaluate_expr(value)?;
+				self.update_var(name, *op, value, Span::default())
+			}
+		}
+	}
+	/// Calls a built-in or user-defined function with already evaluated arguments.
+	pub fn call_function(&mut self, name: &str, args: &[Value], span: Span) -> Result<Value, Error> {
+		if let Some(builtin) = find_builtin(name) {
+			return builtin.call(args, span);
+		}
+		let Some(function) = self.functions.get(name) else {
+			return Err(Error::UndefinedFunction(name.to_string(), span));
+		};
+		if function.params.len() != args.len() {
+			return Err(Error::Type(format!("Function \"{}\" takes {} argument(s), got {}", name, function.params.len(), args.len()), span));
+		}
+		if self.scopes.len() >= MAX_CALL_DEPTH {
+			return Err(Error::Recursion(name.to_string(), span));
+		}
+		let scope = function.params.iter().cloned().zip(args.iter().cloned()).collect();
+		let body = function.body.clone();
+		self.scopes.push(scope);
+		let result = self.evaluate_expr(&body);
+		self.scopes.pop();
+		// Errors inside the body have no position in the source, point at the call instead
+		result.map_err(|err| match err {
+			Error::Recursion(innermost, _) if innermost.is_empty() => Error::Recursion(name.to_string(), span),
+			err if err.span().is_empty() => err.with_span(span),
+			err => err
+		})
+	}
+
+	/// Stores `value` into a global, a compound assignment combines it with the current value first.
+	fn update_var(&mut self, name: &String, op: Option<BinaryOperatorType>, value: Value, span: Span) -> Result<Value, Error> {
+		let value = match op {
+			Some(op) => {
+				let Some(current) = self.get_var(name) else {
+					return Err(Error::UndefinedVariable(name.clone(), span));
+				};
+				let result = apply_binary(op, current.clone(), value, span)?;
+				self.wrap(result)
+			},
+			None => value
+		};
+		self.set_var(name, &value);
+		Ok(value)
+	}
+	/// `ans` and `_` are the last result, `$3` the third.
+	fn get_result(&self, name: &str) -> Option<&Value> {
+		match name {
+			"ans" | "_" => self.results.last(),
+			_ => {
+				let number: usize = name.strip_prefix('$')?.parse().ok()?;
+				self.results.get(number.checked_sub(1)?)
+			}
+		}
+	}
+	fn trace(&self, event: TraceEvent) {
+		if let Some(tracer) = &self.tracer {
+			tracer(&event);
+		}
+	}
+	/// Truncates integers to the word size in programmer mode.
+	fn wrap(&self, value: Value) -> Value {
+		match self.word_size {
+			Some(word_size) => word_size.wrap(value),
+			None => value
+		}
+	}
+
+	/// Parses `name(param, ...) = body` following the `def` keyword.
+	fn define(&mut self, tokens: &[SpannedToken], src: &str) -> Result<(), Error> {
+		let end = Span::new(src.chars().count(), src.chars().count());
+		let Some(SpannedToken { token: Token::Function(name), span: name_span }) = tokens.first() else {
+			let span = tokens.first().map_or(end, |t| t.span);
+			return Err(Error::Parse("Expected function name and parameters after \"def\"".to_string(), span));
+		};
+
+		let mut params = Vec::new();
+		let mut idx = 2;
+		loop {
+			match tokens.get(idx) {
+				Some(SpannedToken { token: Token::CloseParen, .. }) if params.is_empty() => {
+					idx += 1;
+					break;
+				},
+				Some(SpannedToken { token: Token::Identifier(param), .. }) => {
+					params.push(param.clone());
+					match tokens.get(idx + 1) {
+						Some(SpannedToken { token: Token::Comma, .. }) => idx += 2,
+						Some(SpannedToken { token: Token::CloseParen, .. }) => {
+							idx += 2;
+							break;
+						},
+						other => {
+							return Err(Error::Parse("Expected ',' or ')' after parameter".to_string(), other.map_or(end, |t| t.span)));
+						}
+					}
+				},
+				other => {
+					return Err(Error::Parse("Expected parameter name".to_string(), other.map_or(end, |t| t.span)));
+				}
+			}
+		}
+
+		match tokens.get(idx) {
+			Some(SpannedToken { token: Token::Assignment, .. }) => {},
+			other => {
+				return Err(Error::Parse("Expected '=' after parameters".to_string(), other.map_or(end, |t| t.span)));
+			}
+		}
+		let value_queue = self.sort(&tokens[idx + 1..], src)?;
+		let body = Expr::from_rpn(value_queue, src)?;
+		self.define_function(name, params, body).map_err(|err| err.with_span(*name_span))
+	}
+
+	/// Shunting yard: reorders the tokens from infix into reverse polish notation.
+	fn sort(&self, tokens: &[SpannedToken], src: &str) -> Result<Queue<SpannedToken>, Error> {
+		let mut operator_stack: Stack<SpannedToken> = Stack::new();
+		let mut value_queue: Queue<SpannedToken> = Queue::new();
+		// Argument count for every open parenthesis, `None` if it is not a function call
+		let mut paren_args: Stack<Option<usize>> = Stack::new();
+
+		// Operands and operators have to alternate, anything else is not valid infix
+		let mut expect_operand = true;
+
+		self.trace(TraceEvent::Tokens(tokens));
+
+		for (idx, spanned) in tokens.iter().enumerate() {
+			self.trace(TraceEvent::Read(spanned));
+			// Whether the token has to stand where an operand is expected, and whether an operand is expected after it
+			let (is_operand, operand_next) = match spanned.token {
+				Token::Integer(_) | Token::BigInteger(_) | Token::Float(_) | Token::Identifier(_) => (true, false),
+				Token::UnaryOperator(_) | Token::Function(_) | Token::OpenParen => (true, true),
+				// `f()` closes the call where its first argument would be
+				Token::CloseParen if idx >= 2 && tokens[idx - 1].token == Token::OpenParen && matches!(tokens[idx - 2].token, Token::Function(_)) => (true, false),
+				Token::CloseParen => (false, false),
+				Token::BinaryOperator(_) | Token::Assignment | Token::CompoundAssignment(_) | Token::Comma => (false, true),
+				_ => (expect_operand, expect_operand)
+			};
+			if is_operand != expect_operand {
+				// Only the parentheses of a call hold arguments
+				let in_call = matches!(paren_args.peek(), Some(Some(_)));
+				if in_call && matches!(spanned.token, Token::Comma | Token::CloseParen) && matches!(tokens[idx - 1].token, Token::OpenParen | Token::Comma) {
+					return Err(Error::Parse("Empty argument".to_string(), spanned.span));
+				}
+				let expected = if expect_operand { "a value" } else { "an operator" };
+				return Err(Error::Parse(format!("Expected {}, found \"{}\"", expected, spanned.token), spanned.span));
+			}
+			expect_operand = operand_next;
+			match &spanned.token {
+				Token::Identifier(_) if tokens.get(idx + 1).is_some_and(|next| matches!(next.token, Token::Assignment | Token::CompoundAssignment(_))) => {
+					// The variable is stored to by the assignment that follows, not read
+				},
+				Token::Integer(_) | Token::BigInteger(_) | Token::Float(_) | Token::Identifier(_) => {
+					self.output(&mut value_queue, spanned.clone());
+				},
+				Token::UnaryOperator(_) | Token::Function(_) => {
+					self.push_operator(&mut operator_stack, spanned.clone());
+				},
+				Token::Assignment | Token::CompoundAssignment(_) => {
+					let op = match spanned.token {
+						Token::CompoundAssignment(op) => Some(op),
+						_ => None
+					};
+					// Only `name = ...` at the start of an expression, argument or another assignment is valid
+					let before = idx.checked_sub(2).map(|before| &tokens[before].token);
+					match (idx.checked_sub(1).map(|target| &tokens[target]), before) {
+						(Some(SpannedToken { token: Token::Identifier(name), span }), None | Some(Token::Assignment | Token::CompoundAssignment(_) | Token::OpenParen | Token::Comma)) if !name.starts_with('$') => {
+							// Nothing binds looser than an assignment, it stays on the stack until the end of its group
+							self.push_operator(&mut operator_stack, SpannedToken::new(Token::Store(name.clone(), op), span.join(spanned.span)));
+						},
+						_ => { return Err(Error::Parse("Only variables can be assigned to".to_string(), spanned.span)); }
+					}
+				},
+				Token::BinaryOperator(op) => {
+					while let Some(top) = operator_stack.peek() {
+						let top_order = match top.token {
+							Token::UnaryOperator(op_other) => op_other.order(),
+							Token::BinaryOperator(op_other) => op_other.order(),
+							_ => break
+						};
+						if top_order > op.order() || (top_order == op.order() && op.associativity() == Associativity::Left) {
+							let top = self.pop_operator(&mut operator_stack).unwrap();
+							self.output(&mut value_queue, top);
+						} else {
+							break;
+						}
+					}
+					self.push_operator(&mut operator_stack, spanned.clone());
+				},
+				Token::OpenParen => {
+					let is_call = matches!(operator_stack.peek(), Some(SpannedToken { token: Token::Function(_), .. }));
+					paren_args.push(if is_call { Some(1) } else { None });
+					self.push_operator(&mut operator_stack, spanned.clone());
+				},
+				Token::Comma => {
+					while let Some(op) = operator_stack.peek() {
+						if op.token == Token::OpenParen {
+							break;
+						}
+						let op = self.pop_operator(&mut operator_stack).unwrap();
+						self.output(&mut value_queue, op);
+					}
+					match paren_args.pop() {
+						Some(Some(argc)) => paren_args.push(Some(argc + 1)),
+						_ => { return Err(Error::Parse("Unexpected ',' outside of function call".to_string(), spanned.span)); }
+					}
+				},
+				Token::CloseParen => {
+					let mut matched = false;
+					while let Some(op) = self.pop_operator(&mut operator_stack) {
+						match op.token {
+							Token::UnaryOperator(_) => { self.output(&mut value_queue, op) },
+							Token::BinaryOperator(_) => { self.output(&mut value_queue, op) },
+							Token::Store(..) => { self.output(&mut value_queue, op) },
+							Token::OpenParen => {
+								matched = true;
+								break;
+							},
+							_ => { /* Do nothing */ }
+						}
+					}
+					if !matched {
+						return Err(Error::Parse("Unmatched closing parenthesis".to_string(), spanned.span));
+					}
+					if let Some(Some(argc)) = paren_args.pop() {
+						let function = self.pop_operator(&mut operator_stack).unwrap();
+						let Token::Function(name) = &function.token else { unreachable!() };
+						// `f()` has no arguments, anything else has one more than it has commas
+						let argc = if tokens[idx - 1].token == Token::OpenParen { 0 } else { argc };
+						self.output(&mut value_queue, SpannedToken::new(Token::Call(name.clone(), argc), function.span.join(spanned.span)));
+					}
+				},
+				_ => { return Err(Error::Parse(format!("Unexpected token \"{:?}\"", spanned.token), spanned.span)); }
+			}
+		}
+
+		if let Some(last) = tokens.last().filter(|_| expect_operand) {
+			return Err(Error::Parse(format!("Expected a value after \"{}\"", last.token), last.span));
+		}
+
+		self.trace(TraceEvent::End);
+		while let Some(op) = self.pop_operator(&mut operator_stack) {
+			if op.token == Token::OpenParen {
+				return Err(Error::Parse("Unclosed parenthesis".to_string(), op.span));
+			}
+			self.output(&mut value_queue, op);
+		}
+
+		if value_queue.is_empty() {
+			return Err(Error::Parse("No value in result stack!".to_string(), Span::new(0, src.chars().count())));
+		}
+
+		self.trace(TraceEvent::Rpn(&value_queue));
+		Ok(value_queue)
+	}
+	fn push_operator(&self, operator_stack: &mut Stack<SpannedToken>, spanned: SpannedToken) {
+		self.trace(TraceEvent::Push(&spanned.token));
+		operator_stack.push(spanned);
+	}
+	fn pop_operator(&self, operator_stack: &mut Stack<SpannedToken>) -> Option<SpannedToken> {
+		let spanned = operator_stack.pop()?;
+		self.trace(TraceEvent::Pop(&spanned.token));
+		Some(spanned)
+	}
+	fn output(&self, value_queue: &mut Queue<SpannedToken>, spanned: SpannedToken) {
+		self.trace(TraceEvent::Output(&spanned.token));
+		value_queue.enqueue(spanned);
+	}
+	/// Evaluates a queue of tokens in reverse polish notation.
+	fn execute(&mut self, mut value_queue: Queue<SpannedToken>, src: &str) -> Result<Value, Error> {
+		let mut result_stack: Stack<Value> = Stack::new();
+
+		while let Some(spanned) = value_queue.dequeue() {
+			let span = spanned.span;
+			match &spanned.token {
+				Token::Integer(int) => {
+					result_stack.push(self.wrap(Integer(*int)));
+				},
+				Token::BigInteger(big) => {
+					result_stack.push(self.wrap(Value::BigInteger(big.clone())));
+				},
+				Token::Float(flt) => {
+					result_stack.push(Float(*flt));
+				},
+				Token::Identifier(symbol) => {
+					if let Some(var) = self.get_var(symbol) {
+						result_stack.push(self.wrap(var.clone()));
+					} else {
+						return Err(Error::UndefinedVariable(symbol.clone(), span));
+					}
+				},
+				Token::UnaryOperator(op) => {
+					let Some(value) = result_stack.pop() else {
+						return Err(missing_operand(&spanned.token, span));
+					};
+					let result = apply_unary(*op, value, span)?;
+					result_stack.push(self.wrap(result));
+				},
+				Token::BinaryOperator(op) => {
+					if result_stack.len() < 2 {
+						return Err(missing_operand(&spanned.token, span));
+					}
+					let right = result_stack.pop().unwrap();
+					let left = result_stack.pop().unwrap();
+					let result = apply_binary(*op, left, right, span)?;
+					result_stack.push(self.wrap(result));
+				},
+				Token::Call(name, argc) => {
+					if result_stack.len() < *argc {
+						return Err(missing_operand(&spanned.token, span));
+					}
+					let mut args = Vec::with_capacity(*argc);
+					for _ in 0..*argc {
+						args.push(result_stack.pop().unwrap());
+					}
+					args.reverse();
+					let result = self.call_function(name, &args, span)?;
+					result_stack.push(self.wrap(result));
+				},
+				Token::Store(name, op) => {
+					let Some(value) = result_stack.pop() else {
+						return Err(missing_operand(&spanned.token, span));
+					};
+					result_stack.push(self.update_var(name, *op, value, span)?);
+				},
+				_ => { return Err(Error::Parse(format!("Unexpected token \"{:?}\"", spanned.token), span)); }
+			}
+			self.trace(TraceEvent::Evaluate(&spanned.token, &result_stack));
+		}
+
+		let result = result_stack.pop();
+		if result_stack.pop().is_some() {
+			return Err(missing_operator(src));
+		}
+		result.ok_or_else(|| Error::Parse("No value in result stack!".to_string(), Span::new(0, src.chars().count())))
+	}
+}
+
+pub(crate) fn missing_operand(token: &Token, span: Span) -> Error {
+	Error::Parse(format!("Failed to execute operation {:?}: Not enough values in result stack!", token), span)
+}
+pub(crate) fn missing_operator(src: &str) -> Error {
+	Error::Parse("Expected an operator between values".to_string(), Span::new(0, src.chars().count()))
+}
+
+fn apply_unary(op: UnaryOperatorType, value: Value, span: Span) -> Result<Value, Error> {
+	let result = match op {
+		UnaryOperatorType::Negative => -value,
+		UnaryOperatorType::Not => Ok(Integer(if value.as_float() == 0.0 { 1 } else { 0 })),
+		UnaryOperatorType::Invert => !value
+	};
+	result.map_err(|err| err.with_span(span))
+}
+fn apply_binary(op: BinaryOperatorType, left: Value, right: Value, span: Span) -> Result<Value, Error> {
+	let result = match op {
+		BinaryOperatorType::Add => left + right,
+		BinaryOperatorType::Sub => left - right,
+		BinaryOperatorType::Mul => left * right,
+		BinaryOperatorType::Div => left / right,
+		BinaryOperatorType::Mod => left % right,
+		BinaryOperatorType::Exp => left.pow(right),
+		BinaryOperatorType::Fdiv => (left / right).and_then(|value| value.floor()),
+		BinaryOperatorType::And => left & right,
+		BinaryOperatorType::Or => left | right,
+		BinaryOperatorType::Xor => left ^ right,
+		BinaryOperatorType::Shl => left << right,
+		BinaryOperatorType::Shr => left >> right,
+		BinaryOperatorType::Less => Ok(Integer(if left < right { 1 } else { 0 })),
+		BinaryOperatorType::LessEq => Ok(Integer(if left <= right { 1 } else { 0 })),
+		BinaryOperatorType::More => Ok(Integer(if left > right { 1 } else { 0 })),
+		BinaryOperatorType::MoreEq => Ok(Integer(if left >= right { 1 } else { 0 })),
+		BinaryOperatorType::Equal => Ok(Integer(if left == right { 1 } else { 0 })),
+		BinaryOperatorType::NotEqual => Ok(Integer(if left != right { 1 } else { 0 })),
+	};
+	result.map_err(|err| err.with_span(span))
+}
+
+impl Default for Calculator {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -1,5 +1,4 @@
 use std::collections::VecDeque;
-use std::ops::Index;
 
 #[derive(Debug)]
 pub struct Stack<T> {
@@ -21,7 +20,7 @@ impl<T> Stack<T> {
 		self.data.pop_back()
 	}
 	pub fn peek(&self) -> Option<&T> {
-		self.data.get(0)
+		self.data.back()
 	}
 	pub fn len(&self) -> usize {
 		self.data.len()
@@ -32,6 +31,15 @@ impl<T> Stack<T> {
 	pub fn get(&self, idx: usize) -> Option<&T> {
 		self.data.get(idx)
 	}
+	/// Iterates from the bottom of the stack to the top.
+	pub fn iter(&self) -> impl Iterator<Item = &T> {
+		self.data.iter()
+	}
+}
+impl<T> Default for Stack<T> {
+	fn default() -> Self {
+		Self::new()
+	}
 }
 
 #[derive(Debug)]
@@ -62,4 +70,13 @@ impl<T> Queue<T> {
 	pub fn get(&self, idx: usize) -> Option<&T> {
 		self.data.get(idx)
 	}
+	/// Iterates from the front of the queue to the back.
+	pub fn iter(&self) -> impl Iterator<Item = &T> {
+		self.data.iter()
+	}
+}
+impl<T> Default for Queue<T> {
+	fn default() -> Self {
+		Self::new()
+	}
 }
\ No newline at end of file
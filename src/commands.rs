@@ -0,0 +1,118 @@
+use std::fs;
+use shunting_yard_calculator::functions::BUILTINS;
+use crate::script::run_lines;
+use crate::Session;
+use crate::table::print_table;
+
+/// Command of the REPL, run instead of evaluating a line that starts with its name.
+pub struct Command {
+	pub name: &'static str,
+	/// Arguments as shown by `help`, empty if the command takes none.
+	pub usage: &'static str,
+	pub description: &'static str,
+	pub run: fn(&mut Session, &[&str]) -> Result<(), String>
+}
+
+impl Command {
+	pub fn call(&self, session: &mut Session, args: &[&str]) -> Result<(), String> {
+		if self.usage.is_empty() && !args.is_empty() {
+			return Err(format!("Command \"{}\" takes no arguments", self.name));
+		}
+		if !self.usage.is_empty() && args.is_empty() {
+			return Err(format!("Usage: {} {}", self.name, self.usage));
+		}
+		(self.run)(session, args)
+	}
+}
+
+pub fn find_command(name: &str) -> Option<&'static Command> {
+	COMMANDS.iter().find(|command| command.name == name)
+}
+
+pub const COMMANDS: &[Command] = &[
+	Command { name: "vars", usage: "", description: "Lists the variables and functions with their type and value", run: vars },
+	Command { name: "del", usage: "NAME...", description: "Deletes variables or functions", run: del },
+	Command { name: "clear", usage: "", description: "Deletes every variable and function", run: |session, _| {
+		session.calc.clear();
+		Ok(())
+	} },
+	Command { name: "reset", usage: "", description: "Starts over with no variables, functions, results or settings", run: |session, _| {
+		*session = Session { numbered: session.numbered, ..Session::default() };
+		Ok(())
+	} },
+	Command { name: "save", usage: "FILE", description: "Writes the variables and functions to a file", run: save },
+	Command { name: "load", usage: "FILE", description: "Runs a file, e.g. one written by save, without printing its values", run: load },
+	Command { name: "history", usage: "", description: "Lists the numbered results with the lines they came from", run: |session, _| {
+		session.print_history();
+		Ok(())
+	} },
+	Command { name: "help", usage: "", description: "Shows this overview", run: |_, _| {
+		help();
+		Ok(())
+	} }
+];
+
+fn vars(session: &mut Session, _: &[&str]) -> Result<(), String> {
+	let format = session.result_format();
+	let mut rows: Vec<_> = session.calc.globals().iter()
+		.map(|(name, value)| [name.clone(), value.type_name().to_string(), format.format(value)])
+		.collect();
+	rows.extend(session.calc.functions().iter()
+		.map(|(name, function)| [name.clone(), "function".to_string(), format!("{}({}) = {}", name, function.params.join(", "), function.body)]));
+	rows.sort();
+	if rows.is_empty() {
+		println!("No variables or functions defined");
+	} else {
+		print_table(["Name", "Type", "Value"], &rows);
+	}
+	Ok(())
+}
+
+fn del(session: &mut Session, names: &[&str]) -> Result<(), String> {
+	// Nothing is deleted if any of the names is unknown
+	let calc = &mut session.calc;
+	if let Some(unknown) = names.iter().find(|name| !calc.globals().contains_key(**name) && calc.get_function(name).is_none()) {
+		return Err(format!("\"{}\" is neither a variable nor a function", unknown));
+	}
+	for name in names {
+		calc.remove_var(name);
+		calc.remove_function(name);
+	}
+	Ok(())
+}
+
+fn save(session: &mut Session, args: &[&str]) -> Result<(), String> {
+	let [path] = args else {
+		return Err("Usage: save FILE".to_string());
+	};
+	let src = format!("# Session of the shunting yard calculator, read back with `load`\n{}", session.calc.export());
+	fs::write(path, src).map_err(|err| format!("Failed to write \"{}\": {}", path, err))
+}
+
+fn load(session: &mut Session, args: &[&str]) -> Result<(), String> {
+	let [path] = args else {
+		return Err("Usage: load FILE".to_string());
+	};
+	let src = fs::read_to_string(path).map_err(|err| format!("Failed to read \"{}\": {}", path, err))?;
+	// Lines in front of an error stay loaded
+	run_lines(&mut session.calc, &src, |_, _| {})
+		.map_err(|(number, _, error)| format!("{}:{}:{}: {}", path, number, error.span().start + 1, error))
+}
+
+fn help() {
+	println!("Operators    + - * / // % ** & | ^ << >> ~ ! < <= > >= == != ( )");
+	println!("Assignments  = += -= *= /= //= %= **= &= |= ^= <<= >>=");
+	println!("Results      ans _ $N out[N]");
+	println!("Keywords     def set explain hex oct bin dec raw group floatinfo floatinfo32 sci eng fix N sig N");
+	println!("Settings     :word :format :group :precision :echo :trace");
+	let names: Vec<_> = BUILTINS.iter().map(|builtin| builtin.name).collect();
+	for (idx, line) in names.chunks(8).enumerate() {
+		println!("{:<13}{}", if idx == 0 { "Functions" } else { "" }, line.join(" "));
+	}
+	println!();
+	let mut rows: Vec<_> = COMMANDS.iter()
+		.map(|command| [format!("{} {}", command.name, command.usage).trim_end().to_string(), command.description.to_string()])
+		.collect();
+	rows.push(["exit".to_string(), "Leaves the calculator".to_string()]);
+	print_table(["Command", "Description"], &rows);
+}
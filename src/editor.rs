@@ -0,0 +1,177 @@
+use std::borrow::Cow;
+use std::fs;
+use std::path::PathBuf;
+use colored::Colorize;
+use rustyline::completion::Completer;
+use rustyline::highlight::{CmdKind, Highlighter};
+use rustyline::{Context, Helper, Hinter, Validator};
+use shunting_yard_calculator::{Calculator, Token};
+use shunting_yard_calculator::functions::BUILTINS;
+use crate::commands::{find_command, COMMANDS};
+
+/// Display keywords and `:` settings, completed like names together with the commands.
+const KEYWORDS: &[&str] = &[
+	"exit", "def", "set", "explain", "hex", "oct", "bin", "dec", "raw", "group", "sci", "eng", "fix", "sig", "floatinfo", "floatinfo32",
+	":word", ":format", ":group", ":precision", ":echo", ":trace"
+];
+
+/// Line editor support of the REPL, tab completes keywords, variables and functions and highlights the tokens.
+#[derive(Helper, Hinter, Validator)]
+pub struct ReplHelper {
+	/// Keywords, globals and functions, function names end in `(`.
+	names: Vec<String>
+}
+
+impl ReplHelper {
+	pub fn new() -> Self {
+		Self { names: Vec::new() }
+	}
+	/// Picks up the variables and functions defined so far.
+	pub fn update(&mut self, calc: &Calculator) {
+		let keywords = KEYWORDS.iter().chain(COMMANDS.iter().map(|command| &command.name)).map(|keyword| keyword.to_string());
+		let results = ["ans".to_string()].into_iter().filter(|_| !calc.results().is_empty());
+		let globals = calc.globals().keys().cloned().chain(results);
+		let functions = BUILTINS.iter().map(|builtin| builtin.name).chain(calc.functions().keys().map(String::as_str))
+			.map(|name| format!("{}(", name));
+		self.names = keywords.chain(globals).chain(functions).collect();
+		self.names.sort();
+		self.names.dedup();
+	}
+}
+
+impl Completer for ReplHelper {
+	type Candidate = String;
+
+	fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+		// The word under the cursor, `:` only starts a command at the beginning of the line
+		let start = line[..pos].char_indices().rev()
+			.find(|(_, c)| !(c.is_alphanumeric() || *c == '_'))
+			.map_or(0, |(idx, c)| if c == ':' && idx == 0 { 0 } else { idx + c.len_utf8() });
+		let word = &line[start..pos];
+		if word.is_empty() {
+			return Ok((pos, Vec::new()));
+		}
+		let candidates = self.names.iter().filter(|name| name.starts_with(word)).cloned().collect();
+		Ok((start, candidates))
+	}
+}
+
+impl Highlighter for ReplHelper {
+	fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+		// Settings are not expressions
+		if line.starts_with(':') {
+			return Cow::Borrowed(line);
+		}
+		Cow::Owned(highlight(line))
+	}
+	fn highlight_char(&self, _line: &str, _pos: usize, _kind: CmdKind) -> bool {
+		// Any edit can change the tokens or balance the parentheses
+		true
+	}
+}
+
+/// Colours the tokens of a line, unbalanced parentheses and the part the tokenizer fails at are red.
+fn highlight(line: &str) -> String {
+	let chars: Vec<char> = line.chars().collect();
+	let (tokens, error) = match Token::tokenize(line) {
+		Ok(tokens) => (tokens, None),
+		Err(error) => {
+			// Everything in front of the error can still be coloured
+			let start = error.span().start.min(chars.len());
+			let prefix: String = chars[..start].iter().collect();
+			(Token::tokenize(&prefix).unwrap_or_default(), Some(error.span()))
+		}
+	};
+
+	let mut open = Vec::new();
+	let mut unbalanced = Vec::new();
+	for (idx, spanned) in tokens.iter().enumerate() {
+		match spanned.token {
+			Token::OpenParen => open.push(idx),
+			Token::CloseParen if open.pop().is_none() => unbalanced.push(idx),
+			_ => {}
+		}
+	}
+	unbalanced.extend(open);
+
+	let mut highlighted = String::new();
+	let mut end = 0;
+	for (idx, spanned) in tokens.iter().enumerate() {
+		let span = spanned.span;
+		highlighted.extend(&chars[end..span.start]);
+		let text: String = chars[span.start..span.end].iter().collect();
+		let text = match &spanned.token {
+			_ if unbalanced.contains(&idx) => text.red().bold(),
+			Token::Integer(_) | Token::BigInteger(_) | Token::Float(_) => text.cyan(),
+			Token::Identifier(name) if KEYWORDS.contains(&name.as_str()) || find_command(name).is_some() => text.magenta(),
+			Token::Identifier(_) | Token::Function(_) => text.green(),
+			Token::OpenParen | Token::CloseParen => text.blue(),
+			_ => text.yellow()
+		};
+		highlighted.push_str(&text.to_string());
+		end = span.end;
+	}
+	if let Some(span) = error {
+		let start = span.start.clamp(end, chars.len());
+		let stop = span.end.clamp(start, chars.len());
+		highlighted.extend(&chars[end..start]);
+		highlighted.push_str(&chars[start..stop].iter().collect::<String>().red().underline().to_string());
+		end = stop;
+	}
+	highlighted.extend(&chars[end..]);
+	highlighted
+}
+
+/// History file in the config directory, `None` if the platform has none.
+pub fn history_path() -> Option<PathBuf> {
+	let dir = dirs::config_dir()?.join("ShuntingYardCalculator");
+	fs::create_dir_all(&dir).ok()?;
+	Some(dir.join("history.txt"))
+}
+
+#[cfg(test)]
+mod tests {
+	use rustyline::history::DefaultHistory;
+	use super::*;
+
+	fn complete(line: &str) -> (usize, Vec<String>) {
+		let mut helper = ReplHelper::new();
+		ReplHelper::update(&mut helper, &Calculator::new());
+		let history = DefaultHistory::new();
+		helper.complete(line, line.len(), &Context::new(&history)).unwrap()
+	}
+
+	#[test]
+	fn completes_settings_at_the_start_of_the_line() {
+		assert_eq!(complete(":wo"), (0, vec![":word".to_string()]));
+		// `:` in the middle of a line does not start a setting
+		let (start, candidates) = complete("a:b");
+		assert_eq!(start, 2);
+		assert!(candidates.iter().all(|candidate| !candidate.starts_with(':')));
+	}
+
+	#[test]
+	fn completes_the_word_under_the_cursor() {
+		let (start, candidates) = complete("x + s");
+		assert_eq!(start, 4);
+		assert!(candidates.contains(&"sqrt(".to_string()));
+		assert!(candidates.contains(&"set".to_string()));
+		assert_eq!(complete("x + "), (4, Vec::new()));
+	}
+
+	#[test]
+	fn highlights_unbalanced_parentheses() {
+		colored::control::set_override(true);
+		assert_eq!(highlight("(1"), format!("{}{}", "(".red().bold(), "1".cyan()));
+		assert_eq!(highlight("1)"), format!("{}{}", "1".cyan(), ")".red().bold()));
+		assert_eq!(highlight("(x)"), format!("{}{}{}", "(".blue(), "x".green(), ")".blue()));
+	}
+
+	#[test]
+	fn highlights_lex_errors() {
+		colored::control::set_override(true);
+		// The tokens in front of the error keep their colours, the rest of the line stays plain
+		assert_eq!(highlight("1 @ 2"), format!("{} {} 2", "1".cyan(), "@".red().underline()));
+		assert_eq!(highlight("hex 0x + 1"), format!("{} {} + 1", "hex".magenta(), "0x".red().underline()));
+	}
+}
@@ -0,0 +1,109 @@
+use std::fmt::{Display, Formatter};
+
+/// Character range `start..end` in the source of an expression.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct Span {
+	pub start: usize,
+	pub end: usize
+}
+impl Span {
+	pub fn new(start: usize, end: usize) -> Self {
+		Self { start, end }
+	}
+	pub fn len(&self) -> usize {
+		self.end.saturating_sub(self.start)
+	}
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+	/// Smallest span covering both `self` and `other`.
+	pub fn join(self, other: Span) -> Span {
+		Span::new(self.start.min(other.start), self.end.max(other.end))
+	}
+}
+
+/// Reason an arithmetic operation has no result.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ArithmeticError {
+	/// The integer result is too large to be stored.
+	Overflow,
+	DivisionByZero,
+	/// Integers can only be raised to non-negative powers.
+	NegativeExponent,
+	ShiftOutOfRange
+}
+
+impl Display for ArithmeticError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ArithmeticError::Overflow => write!(f, "Integer overflow"),
+			ArithmeticError::DivisionByZero => write!(f, "Division by zero"),
+			ArithmeticError::NegativeExponent => write!(f, "Negative exponent for an integer power"),
+			ArithmeticError::ShiftOutOfRange => write!(f, "Shift amount out of range")
+		}
+	}
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Error {
+	/// The source contains a character or literal that is not a valid token.
+	Lex(String, Span),
+	/// The tokens do not form a valid expression.
+	Parse(String, Span),
+	UndefinedVariable(String, Span),
+	UndefinedFunction(String, Span),
+	/// An operation was applied to values of the wrong type.
+	Type(String, Span),
+	Arithmetic(ArithmeticError, Span),
+	/// Calls of user-defined functions or expressions were nested too deeply.
+	/// The string is the name of the innermost function called, empty outside of functions.
+	Recursion(String, Span)
+}
+
+impl Error {
+	pub fn span(&self) -> Span {
+		match self {
+			Error::Lex(_, span) => *span,
+			Error::Parse(_, span) => *span,
+			Error::UndefinedVariable(_, span) => *span,
+			Error::UndefinedFunction(_, span) => *span,
+			Error::Type(_, span) => *span,
+			Error::Arithmetic(_, span) => *span,
+			Error::Recursion(_, span) => *span
+		}
+	}
+	/// Returns the same error pointing at `span`.
+	pub fn with_span(self, span: Span) -> Self {
+		match self {
+			Error::Lex(msg, _) => Error::Lex(msg, span),
+			Error::Parse(msg, _) => Error::Parse(msg, span),
+			Error::UndefinedVariable(name, _) => Error::UndefinedVariable(name, span),
+			Error::UndefinedFunction(name, _) => Error::UndefinedFunction(name, span),
+			Error::Type(msg, _) => Error::Type(msg, span),
+			Error::Arithmetic(err, _) => Error::Arithmetic(err, span),
+			Error::Recursion(name, _) => Error::Recursion(name, span)
+		}
+	}
+	/// Line of spaces and carets marking the span, to be printed under the source.
+	pub fn underline(&self) -> String {
+		let span = self.span();
+		format!("{}{}", " ".repeat(span.start), "^".repeat(span.len().max(1)))
+	}
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Error::Lex(msg, _) => write!(f, "{}", msg),
+			Error::Parse(msg, _) => write!(f, "{}", msg),
+			Error::UndefinedVariable(name, _) => write!(f, "Variable \"{}\" is undefined!", name),
+			Error::UndefinedFunction(name, _) => write!(f, "Function \"{}\" is undefined!", name),
+			Error::Type(msg, _) => write!(f, "{}", msg),
+			Error::Arithmetic(err, _) => write!(f, "{}", err),
+			Error::Recursion(name, _) if name.is_empty() => write!(f, "Maximum nesting depth exceeded"),
+			Error::Recursion(name, _) => write!(f, "Maximum call depth exceeded in function \"{}\"", name)
+		}
+	}
+}
+
+impl std::error::Error for Error {}
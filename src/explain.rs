@@ -0,0 +1,85 @@
+use std::sync::{Arc, Mutex};
+use shunting_yard_calculator::{Calculator, Error, TraceEvent, Value};
+use crate::table::print_table;
+
+/// Steps of the shunting yard for one statement, rebuilt from the trace events of the calculator.
+#[derive(Default)]
+struct Statement {
+	/// Token read, actions taken, operator stack and output queue after the actions.
+	rows: Vec<[String; 4]>,
+	/// Mirror of the operator stack, kept up to date from push and pop events.
+	stack: Vec<String>,
+	queue: Vec<String>,
+	/// Token evaluated and the result stack after it.
+	evaluation: Vec<[String; 2]>
+}
+
+impl Statement {
+	fn act(&mut self, action: String) {
+		let Some(row) = self.rows.last_mut() else {
+			return;
+		};
+		if !row[1].is_empty() {
+			row[1].push_str(", ");
+		}
+		row[1].push_str(&action);
+		row[2] = self.stack.join(" ");
+		row[3] = self.queue.join(" ");
+	}
+	fn record(&mut self, event: &TraceEvent) {
+		match event {
+			TraceEvent::Tokens(_) | TraceEvent::Rpn(_) => {},
+			TraceEvent::Read(spanned) => self.rows.push([spanned.token.to_string(), String::new(), self.stack.join(" "), self.queue.join(" ")]),
+			TraceEvent::Push(token) => {
+				self.stack.push(token.to_string());
+				self.act(format!("push {}", token));
+			},
+			TraceEvent::Pop(token) => {
+				self.stack.pop();
+				self.act(format!("pop {}", token));
+			},
+			TraceEvent::Output(token) => {
+				self.queue.push(token.to_string());
+				self.act(format!("output {}", token));
+			},
+			TraceEvent::End => self.rows.push(["end".to_string(), String::new(), self.stack.join(" "), self.queue.join(" ")]),
+			TraceEvent::Evaluate(token, stack) => {
+				let values: Vec<_> = stack.iter().map(Value::to_string).collect();
+				self.evaluation.push([token.to_string(), values.join(" ")]);
+			}
+		}
+	}
+	fn print(&self) {
+		print_table(["Token", "Action", "Stack", "Queue"], &self.rows);
+		if self.evaluation.is_empty() {
+			return;
+		}
+		println!();
+		println!("RPN: {}", self.queue.join(" "));
+		print_table(["Token", "Result stack"], &self.evaluation);
+	}
+}
+
+/// Evaluates `line` like `Calculator::calculate_all` and prints every step of the shunting yard and of the evaluation.
+/// Steps up to an error are printed as well.
+pub fn explain(calc: &mut Calculator, line: &str) -> Result<Vec<Value>, Error> {
+	let statements: Arc<Mutex<Vec<Statement>>> = Arc::default();
+	let recorder = statements.clone();
+	let previous = calc.set_tracer(Some(Box::new(move |event| {
+		let mut statements = recorder.lock().unwrap();
+		if let TraceEvent::Tokens(_) = event {
+			statements.push(Statement::default());
+		}
+		if let Some(statement) = statements.last_mut() {
+			statement.record(event);
+		}
+	})));
+	let result = calc.calculate_all(line);
+	calc.set_tracer(previous);
+
+	for statement in statements.lock().unwrap().iter() {
+		statement.print();
+		println!();
+	}
+	result
+}
@@ -0,0 +1,359 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, Signed};
+use crate::token::NumberBaseType;
+use crate::value::Value;
+use crate::word::WordSize;
+
+/// How the digits of a decimal result are laid out.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum Notation {
+	/// Integers in full, floats as printed by Rust.
+	#[default]
+	Default,
+	/// One digit before the decimal point and an exponent, e.g. `1.234e5`.
+	Scientific,
+	/// Exponent that is a multiple of three, written as an SI prefix where one exists, e.g. `123.4k`.
+	Engineering,
+	/// Fixed number of decimal places.
+	Fixed(usize),
+	/// Rounded to a number of significant digits.
+	Significant(usize)
+}
+
+/// Most digits `fix N` and `sig N` may ask for, a double has at most 767 significant decimal digits.
+pub const MAX_DIGITS: usize = 1000;
+
+/// Parses `default`, `sci`, `eng`, `fix N` and `sig N`.
+impl FromStr for Notation {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut words = s.split_whitespace();
+		let notation = match words.next() {
+			Some("default") => Notation::Default,
+			Some("sci") => Notation::Scientific,
+			Some("eng") => Notation::Engineering,
+			Some(keyword @ ("fix" | "sig")) => {
+				let digits = words.next().and_then(|digits| digits.parse::<usize>().ok()).filter(|digits| *digits <= MAX_DIGITS);
+				match (keyword, digits) {
+					("fix", Some(digits)) => Notation::Fixed(digits),
+					("sig", Some(digits)) if digits > 0 => Notation::Significant(digits),
+					_ => { return Err(format!("Expected a number of digits after \"{}\"", keyword)); }
+				}
+			},
+			_ => { return Err(format!("Unknown format \"{}\", expected default, sci, eng, fix N or sig N", s.trim())); }
+		};
+		match words.next() {
+			Some(extra) => Err(format!("Unexpected \"{}\" after format", extra)),
+			None => Ok(notation)
+		}
+	}
+}
+
+impl Display for Notation {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Notation::Default => write!(f, "default"),
+			Notation::Scientific => write!(f, "sci"),
+			Notation::Engineering => write!(f, "eng"),
+			Notation::Fixed(places) => write!(f, "fix {}", places),
+			Notation::Significant(count) => write!(f, "sig {}", count)
+		}
+	}
+}
+
+/// Display settings for results.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct NumberFormat {
+	/// Layout of decimal results, ignored for other bases.
+	pub notation: Notation,
+	/// Separator inserted between groups of three digits before the decimal point.
+	pub separator: Option<char>,
+	pub base: NumberBaseType,
+	/// Integers are shown as two's complement at this width in binary, octal and hex.
+	pub word_size: Option<WordSize>,
+	/// Maximum number of binary or octal digits after the point of a float, all of them if `None`.
+	pub precision: Option<usize>,
+	/// Shows the IEEE-754 bit pattern of floats instead of their value.
+	pub raw_bits: bool
+}
+
+impl NumberFormat {
+	pub fn new(notation: Notation, separator: Option<char>) -> Self {
+		Self { notation, separator, ..Self::default() }
+	}
+	pub fn format(&self, value: &Value) -> String {
+		match (value, self.raw_bits) {
+			(Value::Float(flt), true) => self.format_bits(flt.to_bits()),
+			_ if self.base != NumberBaseType::Decimal => self.format_in_base(value),
+			_ => self.format_decimal(value)
+		}
+	}
+	/// Integers in binary, octal or hex, floats as hex floats or with binary or octal digits after the point.
+	fn format_in_base(&self, value: &Value) -> String {
+		match value {
+			Value::Float(flt) if !flt.is_finite() => value.to_string(),
+			Value::Float(flt) => {
+				match self.base {
+					NumberBaseType::Binary => float_in_base(*flt, 2, self.precision),
+					NumberBaseType::Octal => float_in_base(*flt, 8, self.precision),
+					_ => hex_float(*flt)
+				}
+			},
+			Value::Integer(_) | Value::BigInteger(_) => {
+				let int = value.as_bigint().unwrap();
+				match self.word_size {
+					Some(word_size) => {
+						let bits = word_size.bits() as usize;
+						let pattern = word_size.bit_pattern(value).unwrap_or(int);
+						match self.base {
+							NumberBaseType::Binary => format!("0b{:0width$b}", pattern, width = bits),
+							NumberBaseType::Octal => format!("0o{:0width$o}", pattern, width = bits.div_ceil(3)),
+							_ => format!("0x{:0width$X}", pattern, width = bits / 4)
+						}
+					},
+					// A plain integer shows its 64 bit two's complement, only big integers have a sign
+					None => match (value, self.base) {
+						(Value::Integer(int), NumberBaseType::Binary) => format!("{:#b}", int),
+						(Value::Integer(int), NumberBaseType::Octal) => format!("{:#o}", int),
+						(Value::Integer(int), _) => format!("{:#X}", int),
+						(_, NumberBaseType::Binary) => format!("{:#b}", int),
+						(_, NumberBaseType::Octal) => format!("{:#o}", int),
+						_ => format!("{:#X}", int)
+					}
+				}
+			},
+			Value::Undefined => value.to_string()
+		}
+	}
+	/// Bits of a double, in binary with the sign, exponent and mantissa fields separated.
+	fn format_bits(&self, bits: u64) -> String {
+		match self.base {
+			NumberBaseType::Decimal => format!("{}", bits),
+			NumberBaseType::Binary => format!("0b{}_{:011b}_{:052b}", bits >> 63, (bits >> 52) & 0x7FF, bits & MANTISSA_MASK),
+			NumberBaseType::Octal => format!("{:#o}", bits),
+			NumberBaseType::Hex => format!("0x{:016X}", bits)
+		}
+	}
+	fn format_decimal(&self, value: &Value) -> String {
+		let Some(digits) = Digits::of(value) else {
+			return value.to_string();
+		};
+		let sign = if digits.negative { "-" } else { "" };
+		let (int_part, frac_part, suffix) = match self.notation {
+			Notation::Default => {
+				let text = value.to_string();
+				let text = text.trim_start_matches('-');
+				match text.split_once('.') {
+					Some((int_part, frac_part)) => (int_part.to_string(), frac_part.to_string(), String::new()),
+					None => (text.to_string(), String::new(), String::new())
+				}
+			},
+			Notation::Scientific => {
+				let (int_part, frac_part) = digits.mantissa(1);
+				(int_part, frac_part, format!("e{}", digits.exponent))
+			},
+			Notation::Engineering => {
+				let exponent = digits.exponent.div_euclid(3) * 3;
+				let (int_part, frac_part) = digits.mantissa((digits.exponent - exponent + 1) as usize);
+				let suffix = match si_prefix(exponent) {
+					Some(prefix) => prefix.to_string(),
+					None => format!("e{}", exponent)
+				};
+				(int_part, frac_part, suffix)
+			},
+			Notation::Fixed(places) => {
+				match value {
+					Value::Float(flt) => {
+						let text = format!("{:.*}", places, flt.abs());
+						let (int_part, frac_part) = text.split_once('.').unwrap_or((&text, ""));
+						(int_part.to_string(), frac_part.to_string(), String::new())
+					},
+					_ => {
+						let (int_part, _) = digits.positional(0);
+						(int_part, "0".repeat(places), String::new())
+					}
+				}
+			},
+			Notation::Significant(count) => {
+				let digits = digits.round(count);
+				// Trailing zeros after the decimal point are significant for floats
+				let min_digits = if matches!(value, Value::Float(_)) { count } else { 0 };
+				let (int_part, frac_part) = digits.positional(min_digits);
+				(int_part, frac_part, String::new())
+			}
+		};
+
+		let int_part = group(&int_part, self.separator);
+		if frac_part.is_empty() {
+			format!("{}{}{}", sign, int_part, suffix)
+		} else {
+			format!("{}{}.{}{}", sign, int_part, frac_part, suffix)
+		}
+	}
+}
+
+const MANTISSA_MASK: u64 = (1 << 52) - 1;
+
+/// C99 hexadecimal float notation, e.g. `0x1.8p+1` for 3.
+pub fn hex_float(flt: f64) -> String {
+	let sign = if flt.is_sign_negative() { "-" } else { "" };
+	let bits = flt.to_bits();
+	let biased_exponent = ((bits >> 52) & 0x7FF) as i64;
+	let mantissa = bits & MANTISSA_MASK;
+	let (lead, exponent) = match (biased_exponent, mantissa) {
+		(0, 0) => (0, 0),
+		// Subnormal numbers have no implicit leading one
+		(0, _) => (0, -1022),
+		_ => (1, biased_exponent - 1023)
+	};
+	let fraction = format!("{:013x}", mantissa);
+	let fraction = fraction.trim_end_matches('0');
+	if fraction.is_empty() {
+		format!("{}0x{}p{:+}", sign, lead, exponent)
+	} else {
+		format!("{}0x{}.{}p{:+}", sign, lead, fraction, exponent)
+	}
+}
+
+/// Float with its digits in `radix` before and after the point, e.g. `0b11.01` for 3.25.
+/// Every finite float has a finite expansion in base 2 and 8, `precision` truncates it.
+pub fn float_in_base(flt: f64, radix: u32, precision: Option<usize>) -> String {
+	let sign = if flt < 0.0 { "-" } else { "" };
+	let prefix = match radix {
+		2 => "0b",
+		8 => "0o",
+		16 => "0x",
+		_ => ""
+	};
+	let int_part = BigInt::from_f64(flt.abs().trunc()).unwrap_or_default().to_str_radix(radix);
+	// Multiplying by a power of two and removing the integer part are exact
+	let mut frac = flt.abs().fract();
+	let mut frac_part = String::new();
+	while frac > 0.0 && precision.is_none_or(|precision| frac_part.len() < precision) {
+		frac *= radix as f64;
+		let digit = frac.trunc();
+		frac_part.push(char::from_digit(digit as u32, radix).unwrap());
+		frac -= digit;
+	}
+	if frac_part.is_empty() {
+		format!("{}{}{}", sign, prefix, int_part)
+	} else {
+		format!("{}{}{}.{}", sign, prefix, int_part, frac_part)
+	}
+}
+
+/// SI prefix for a power of ten that is a multiple of three, nothing for `10^0`.
+fn si_prefix(exponent: i64) -> Option<&'static str> {
+	const PREFIXES: [&str; 21] = [
+		"q", "r", "y", "z", "a", "f", "p", "n", "µ", "m", "",
+		"k", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"
+	];
+	if exponent % 3 != 0 {
+		return None;
+	}
+	usize::try_from(exponent / 3 + 10).ok().and_then(|idx| PREFIXES.get(idx).copied())
+}
+
+/// Inserts `separator` between every group of three digits, counted from the right.
+fn group(digits: &str, separator: Option<char>) -> String {
+	let Some(separator) = separator else {
+		return digits.to_string();
+	};
+	let mut result = String::new();
+	for (i, c) in digits.chars().enumerate() {
+		if i > 0 && (digits.len() - i).is_multiple_of(3) {
+			result.push(separator);
+		}
+		result.push(c);
+	}
+	result
+}
+
+/// Decimal digits of a finite number, the value is `d.ddd * 10^exponent`.
+struct Digits {
+	negative: bool,
+	/// Without leading or trailing zeros, except for a single zero.
+	digits: Vec<u8>,
+	exponent: i64
+}
+
+impl Digits {
+	fn of(value: &Value) -> Option<Digits> {
+		let (negative, text, exponent) = match value {
+			Value::Float(flt) if flt.is_finite() => {
+				let text = format!("{:e}", flt.abs());
+				let (mantissa, exponent) = text.split_once('e')?;
+				(*flt < 0.0, mantissa.replace('.', ""), exponent.parse().ok()?)
+			},
+			Value::Integer(_) | Value::BigInteger(_) => {
+				let big = value.as_bigint()?;
+				let text = big.magnitude().to_string();
+				let exponent = text.len() as i64 - 1;
+				(big.is_negative(), text, exponent)
+			},
+			_ => { return None; }
+		};
+		let mut digits: Vec<u8> = text.bytes().map(|b| b - b'0').collect();
+		while digits.len() > 1 && digits.last() == Some(&0) {
+			digits.pop();
+		}
+		Some(Digits { negative, digits, exponent })
+	}
+	/// Rounds half away from zero to `count` significant digits.
+	fn round(mut self, count: usize) -> Digits {
+		if self.digits.len() <= count {
+			return self;
+		}
+		let round_up = self.digits[count] >= 5;
+		self.digits.truncate(count);
+		if round_up {
+			let mut idx = count;
+			loop {
+				if idx == 0 {
+					// Every digit was a nine, e.g. 999 becomes 1000
+					self.digits.insert(0, 1);
+					self.digits.truncate(count);
+					self.exponent += 1;
+					break;
+				}
+				idx -= 1;
+				if self.digits[idx] == 9 {
+					self.digits[idx] = 0;
+				} else {
+					self.digits[idx] += 1;
+					break;
+				}
+			}
+		}
+		while self.digits.len() > 1 && self.digits.last() == Some(&0) {
+			self.digits.pop();
+		}
+		self
+	}
+	/// Digits before and after the decimal point, with `int_digits` digits before it.
+	fn mantissa(&self, int_digits: usize) -> (String, String) {
+		let text = self.text(int_digits);
+		(text[..int_digits].to_string(), text[int_digits..].to_string())
+	}
+	/// Digits before and after the decimal point without an exponent, using at least `min_digits` digits.
+	fn positional(&self, min_digits: usize) -> (String, String) {
+		let text = self.text(min_digits);
+		if self.exponent < 0 {
+			return ("0".to_string(), format!("{}{}", "0".repeat((-self.exponent - 1) as usize), text));
+		}
+		let int_digits = self.exponent as usize + 1;
+		if text.len() <= int_digits {
+			(format!("{}{}", text, "0".repeat(int_digits - text.len())), String::new())
+		} else {
+			(text[..int_digits].to_string(), text[int_digits..].to_string())
+		}
+	}
+	/// The digits as text, padded with zeros to at least `min_digits`.
+	fn text(&self, min_digits: usize) -> String {
+		let text: String = self.digits.iter().map(|d| char::from(b'0' + d)).collect();
+		format!("{:0<width$}", text, width = min_digits)
+	}
+}
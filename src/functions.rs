@@ -0,0 +1,120 @@
+use num_bigint::BigInt;
+use num_traits::{One, ToPrimitive};
+use crate::ast::Expr;
+use crate::error::{Error, Span};
+use crate::value::Value;
+use crate::value::Value::{Float, Integer, Undefined};
+
+/// Function available in every expression without being defined first.
+pub struct Builtin {
+	pub name: &'static str,
+	pub min_args: usize,
+	/// `None` if the function takes any number of arguments.
+	pub max_args: Option<usize>,
+	pub func: fn(&[Value]) -> Result<Value, Error>
+}
+
+impl Builtin {
+	pub fn accepts(&self, argc: usize) -> bool {
+		argc >= self.min_args && self.max_args.is_none_or(|max| argc <= max)
+	}
+	/// Human readable form of the accepted argument count, e.g. "1 or 2".
+	pub fn arity(&self) -> String {
+		match self.max_args {
+			Some(max) if max == self.min_args => format!("{}", max),
+			Some(max) if max == self.min_args + 1 => format!("{} or {}", self.min_args, max),
+			Some(max) => format!("{} to {}", self.min_args, max),
+			None => format!("at least {}", self.min_args)
+		}
+	}
+	pub fn call(&self, args: &[Value], span: Span) -> Result<Value, Error> {
+		if !self.accepts(args.len()) {
+			return Err(Error::Type(format!("Function \"{}\" takes {} argument(s), got {}", self.name, self.arity(), args.len()), span));
+		}
+		if args.iter().any(|arg| arg.is_undefined()) {
+			return Ok(Undefined);
+		}
+		(self.func)(args).map_err(|err| err.with_span(span))
+	}
+}
+
+/// Function defined with `def name(params) = body`.
+#[derive(Clone, Debug)]
+pub struct UserFunction {
+	pub params: Vec<String>,
+	pub body: Expr
+}
+
+pub fn find_builtin(name: &str) -> Option<&'static Builtin> {
+	BUILTINS.iter().find(|builtin| builtin.name == name)
+}
+
+fn float_fn(args: &[Value], f: fn(f64) -> f64) -> Result<Value, Error> {
+	Ok(Float(f(args[0].as_float())))
+}
+
+/// Reads an integer argument as a pattern of `bits` bits, negative values as two's complement.
+fn bit_pattern_arg(name: &str, arg: &Value, bits: u32) -> Result<u64, Error> {
+	let Some(big) = arg.as_bigint() else {
+		return Err(Error::Type(format!("Function \"{}\" expects an integer bit pattern", name), Span::default()));
+	};
+	let limit: BigInt = BigInt::one() << bits;
+	let min: BigInt = -(BigInt::one() << (bits - 1));
+	if big >= limit || big < min {
+		return Err(Error::Type(format!("Bit pattern for function \"{}\" does not fit into {} bits", name, bits), Span::default()));
+	}
+	Ok((big & (limit - BigInt::one())).to_u64().unwrap())
+}
+
+pub const BUILTINS: &[Builtin] = &[
+	Builtin { name: "sqrt", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::sqrt) },
+	Builtin { name: "cbrt", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::cbrt) },
+	Builtin { name: "abs", min_args: 1, max_args: Some(1), func: |args| {
+		match &args[0] {
+			Float(_) => float_fn(args, f64::abs),
+			int if *int < Integer(0) => -int.clone(),
+			int => Ok(int.clone())
+		}
+	} },
+	Builtin { name: "min", min_args: 1, max_args: None, func: |args| {
+		Ok(args.iter().skip(1).fold(args[0].clone(), |min, arg| if *arg < min { arg.clone() } else { min }))
+	} },
+	Builtin { name: "max", min_args: 1, max_args: None, func: |args| {
+		Ok(args.iter().skip(1).fold(args[0].clone(), |max, arg| if *arg > max { arg.clone() } else { max }))
+	} },
+	Builtin { name: "floor", min_args: 1, max_args: Some(1), func: |args| args[0].floor() },
+	Builtin { name: "ceil", min_args: 1, max_args: Some(1), func: |args| args[0].ceil() },
+	Builtin { name: "round", min_args: 1, max_args: Some(1), func: |args| args[0].round() },
+	Builtin { name: "ln", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::ln) },
+	Builtin { name: "log", min_args: 1, max_args: Some(2), func: |args| {
+		match args.get(1) {
+			Some(base) => Ok(Float(args[0].as_float().log(base.as_float()))),
+			None => float_fn(args, f64::log10)
+		}
+	} },
+	Builtin { name: "log2", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::log2) },
+	Builtin { name: "exp", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::exp) },
+	Builtin { name: "sin", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::sin) },
+	Builtin { name: "cos", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::cos) },
+	Builtin { name: "tan", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::tan) },
+	Builtin { name: "asin", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::asin) },
+	Builtin { name: "acos", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::acos) },
+	Builtin { name: "atan", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::atan) },
+	Builtin { name: "atan2", min_args: 2, max_args: Some(2), func: |args| Ok(Float(args[0].as_float().atan2(args[1].as_float()))) },
+	Builtin { name: "sinh", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::sinh) },
+	Builtin { name: "cosh", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::cosh) },
+	Builtin { name: "tanh", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::tanh) },
+	Builtin { name: "asinh", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::asinh) },
+	Builtin { name: "acosh", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::acosh) },
+	Builtin { name: "atanh", min_args: 1, max_args: Some(1), func: |args| float_fn(args, f64::atanh) },
+	Builtin { name: "f32bits", min_args: 1, max_args: Some(1), func: |args| Ok(Integer(i64::from((args[0].as_float() as f32).to_bits()))) },
+	Builtin { name: "f64bits", min_args: 1, max_args: Some(1), func: |args| Ok(Value::from(BigInt::from(args[0].as_float().to_bits()))) },
+	Builtin { name: "fromf32bits", min_args: 1, max_args: Some(1), func: |args| {
+		let bits = bit_pattern_arg("fromf32bits", &args[0], 32)?;
+		Ok(Float(f64::from(f32::from_bits(bits as u32))))
+	} },
+	Builtin { name: "fromf64bits", min_args: 1, max_args: Some(1), func: |args| {
+		let bits = bit_pattern_arg("fromf64bits", &args[0], 64)?;
+		Ok(Float(f64::from_bits(bits)))
+	} },
+];
@@ -0,0 +1,68 @@
+use std::fmt::{Display, Formatter};
+
+/// Fields of an IEEE-754 binary float, for single or double precision.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct FloatInfo {
+	pub negative: bool,
+	/// Exponent field as stored, with the bias added.
+	pub biased_exponent: u64,
+	/// Fraction field, without the implicit leading one.
+	pub mantissa: u64,
+	pub exponent_bits: u32,
+	pub mantissa_bits: u32
+}
+
+impl FloatInfo {
+	pub fn of_f64(flt: f64) -> Self {
+		Self::from_bits(flt.to_bits(), 11, 52)
+	}
+	pub fn of_f32(flt: f32) -> Self {
+		Self::from_bits(u64::from(flt.to_bits()), 8, 23)
+	}
+	fn from_bits(bits: u64, exponent_bits: u32, mantissa_bits: u32) -> Self {
+		Self {
+			negative: bits >> (exponent_bits + mantissa_bits) & 1 == 1,
+			biased_exponent: bits >> mantissa_bits & ((1 << exponent_bits) - 1),
+			mantissa: bits & ((1 << mantissa_bits) - 1),
+			exponent_bits,
+			mantissa_bits
+		}
+	}
+	pub fn bias(&self) -> i64 {
+		(1 << (self.exponent_bits - 1)) - 1
+	}
+	pub fn bits(&self) -> u64 {
+		u64::from(self.negative) << (self.exponent_bits + self.mantissa_bits) | self.biased_exponent << self.mantissa_bits | self.mantissa
+	}
+	pub fn class(&self) -> &'static str {
+		let max_exponent = (1 << self.exponent_bits) - 1;
+		match (self.biased_exponent, self.mantissa) {
+			(0, 0) => "zero",
+			(0, _) => "subnormal",
+			(exponent, 0) if exponent == max_exponent => "infinite",
+			(exponent, _) if exponent == max_exponent => "nan",
+			_ => "normal"
+		}
+	}
+	/// Power of two the significand is multiplied with, subnormals share the smallest normal exponent.
+	pub fn exponent(&self) -> i64 {
+		self.biased_exponent.max(1) as i64 - self.bias()
+	}
+	/// The mantissa with its leading bit, in the range `[1, 2)` for normal numbers.
+	pub fn significand(&self) -> f64 {
+		let lead = if self.biased_exponent == 0 { 0.0 } else { 1.0 };
+		lead + self.mantissa as f64 / (1u64 << self.mantissa_bits) as f64
+	}
+}
+
+/// One line per field, e.g. `exponent  0b01111111111  1023  2^0`.
+impl Display for FloatInfo {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let total_bits = (1 + self.exponent_bits + self.mantissa_bits) as usize;
+		writeln!(f, "bits      0x{:0width$X}", self.bits(), width = total_bits / 4)?;
+		writeln!(f, "sign      {}  {}", u8::from(self.negative), if self.negative { "-" } else { "+" })?;
+		writeln!(f, "exponent  0b{:0width$b}  {}  2^{}", self.biased_exponent, self.biased_exponent, self.exponent(), width = self.exponent_bits as usize)?;
+		writeln!(f, "mantissa  0x{:0width$X}  {:?}", self.mantissa, self.significand(), width = (self.mantissa_bits as usize).div_ceil(4))?;
+		write!(f, "class     {}", self.class())
+	}
+}
@@ -0,0 +1,30 @@
+pub mod token;
+pub mod ast;
+pub mod collections;
+pub mod error;
+pub mod format;
+pub mod functions;
+pub mod ieee;
+pub mod trace;
+pub mod value;
+pub mod word;
+pub mod calculator;
+
+pub use crate::ast::Expr;
+pub use crate::calculator::Calculator;
+pub use crate::error::{ArithmeticError, Error, Span};
+pub use crate::format::{Notation, NumberFormat};
+pub use crate::token::Token;
+pub use crate::trace::{TraceEvent, Tracer};
+pub use crate::value::Value;
+pub use crate::word::WordSize;
+
+/// Evaluates a single expression with a fresh calculator, without any variables defined.
+pub fn evaluate(src: &str) -> Result<Value, Error> {
+	Calculator::new().calculate(src)
+}
+
+/// Parses a single expression into a tree without evaluating it.
+pub fn parse(src: &str) -> Result<Expr, Error> {
+	Calculator::new().parse(src)
+}
@@ -1,741 +1,361 @@
 extern crate colored;
 
-pub mod token;
-mod collections;
-
-use std::cmp::Ordering;
-use std::collections::{HashMap, VecDeque};
-use std::fmt::{Display, format, Formatter};
-use std::io;
-use std::io::{BufRead, Write};
-use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Rem, Shl, Shr, Sub};
-use crate::token::*;
-use crate::Value::{Float, Integer, Undefined};
+mod commands;
+mod editor;
+mod explain;
+mod script;
+mod table;
+
+use std::{env, io};
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, IsTerminal};
+use std::process::ExitCode;
 use colored::Colorize;
-use crate::collections::{Queue, Stack};
-
-#[derive(Debug)]
-pub enum Value {
-	Undefined,
-	Integer(i64),
-	Float(f64)
-}
-
-impl Value {
-	pub fn is_undefined(&self) -> bool {
-		match self {
-			Undefined => true,
-			_ => false
-		}
-	}
-	pub fn pow(&self, rhs: Value) -> Value {
-		match self {
-			Integer(i_left) => {
-				match rhs {
-					Integer(i_right) => {
-						Integer(i_left.pow((i_right) as u32))
-					},
-					Float(f_right) => {
-						Float((*i_left as f64).powf(f_right))
-					}
-					Undefined => Undefined
-				}
-			},
-			Float(f_left) => {
-				match rhs {
-					Integer(i_right) => {
-						Float(f_left.powi(i_right as i32))
-					},
-					Float(f_right) => {
-						Float(f_left.powf(f_right))
-					}
-					Undefined => Undefined
-				}
-			}
-			Undefined => Undefined
-		}
-	}
-	pub fn floor(&self) -> Value {
-		match self {
-			Undefined => Undefined,
-			Integer(int) => Integer(int.clone()),
-			Float(flt) => Integer(flt.floor() as i64)
-		}
-	}
-	pub fn as_float(&self) -> f64 {
-		match self {
-			Undefined => 0.0f64,
-			Integer(int) => int.clone() as f64,
-			Float(flt) => flt.clone()
-		}
+use rustyline::error::ReadlineError;
+use rustyline::history::DefaultHistory;
+use rustyline::Editor;
+use shunting_yard_calculator::{Calculator, Error, Notation, NumberFormat, Token, Value, WordSize};
+use shunting_yard_calculator::ieee::FloatInfo;
+use shunting_yard_calculator::token::NumberBaseType;
+use crate::commands::find_command;
+use crate::editor::ReplHelper;
+use crate::table::print_table;
+
+const PROMPT: &str = ">>> ";
+
+/// Splits a leading `sci`, `eng`, `fix N` or `sig N` display prefix off the line.
+fn split_notation(line: &str) -> Option<(Notation, &str)> {
+	let (keyword, rest) = line.split_once(' ')?;
+	let words = match keyword {
+		"sci" | "eng" => 1,
+		"fix" | "sig" => 2,
+		_ => { return None; }
+	};
+	let mut end = keyword.len();
+	if words == 2 {
+		let digits = rest.trim_start().split(' ').next()?;
+		end = line.len() - rest.trim_start().len() + digits.len();
 	}
+	let notation = line[..end].parse().ok()?;
+	Some((notation, line[end..].trim_start()))
 }
-impl Clone for Value {
-	fn clone(&self) -> Self {
-		match self {
-			Undefined => Undefined,
-			Integer(int) => Integer(int.clone()),
-			Float(flt) => Float(flt.clone())
-		}
-	}
-}
-
-impl Neg for Value {
-	type Output = Value;
 
-	fn neg(self) -> Self::Output {
-		match self {
-			Undefined => Undefined,
-			Integer(int) => { Integer(-int) },
-			Float(flt) => { Float(-flt) }
-		}
-	}
+/// Settings changed by `:` commands, kept for the rest of the session.
+#[derive(Default)]
+struct Settings {
+	format: NumberFormat,
+	/// Prints the value of every statement on a line instead of only the last one.
+	echo_all: bool
 }
-impl Add for Value {
-	type Output = Value;
 
-	fn add(self, rhs: Self) -> Self::Output {
-		match self {
-			Integer(i_left) => {
-				match rhs {
-					Integer(i_right) => {
-						Integer(i_left + i_right)
-					},
-					Float(f_right) => {
-						Float(i_left as f64 + f_right)
-					}
-					Undefined => Undefined
-				}
-			},
-			Float(f_left) => {
-				match rhs {
-					Integer(i_right) => {
-						Float(f_left + (i_right as f64))
-					},
-					Float(f_right) => {
-						Float(f_left + f_right)
-					}
-					Undefined => Undefined
-				}
-			}
-			Undefined => Undefined
-		}
+/// Runs a `:` command that changes a setting for the rest of the session.
+fn session_command(command: &str, calc: &mut Calculator, settings: &mut Settings) -> Result<(), String> {
+	let (name, arg) = command.split_once(' ').unwrap_or((command, ""));
+	let arg = arg.trim();
+	let format = &mut settings.format;
+	match (name, arg) {
+		("word", "") => println!("{}", calc.word_size().map_or("off".to_string(), |word_size| word_size.to_string())),
+		("word", "off") => calc.set_word_size(None),
+		("word", arg) => calc.set_word_size(Some(arg.parse::<WordSize>()?)),
+		("format", "") => println!("{}", format.notation),
+		("format", arg) => format.notation = arg.parse()?,
+		("group", "off") => format.separator = None,
+		("group", "") => format.separator = Some(','),
+		("group", "space") => format.separator = Some(' '),
+		("group", arg) if arg.chars().count() == 1 => format.separator = arg.chars().next(),
+		("group", arg) => { return Err(format!("Expected a single separator character, got \"{}\"", arg)); },
+		("precision", "") => println!("{}", format.precision.map_or("off".to_string(), |precision| precision.to_string())),
+		("precision", "off") => format.precision = None,
+		("precision", arg) => format.precision = Some(arg.parse().map_err(|_| format!("Expected a number of digits, got \"{}\"", arg))?),
+		("echo", "") => println!("{}", if settings.echo_all { "all" } else { "last" }),
+		("echo", "all") => settings.echo_all = true,
+		("echo", "last") => settings.echo_all = false,
+		("echo", arg) => { return Err(format!("Expected \"all\" or \"last\", got \"{}\"", arg)); },
+		("trace", "") => println!("{}", if calc.is_tracing() { "on" } else { "off" }),
+		("trace", "on") => { calc.set_tracer(Some(Box::new(|event| println!("{}", event)))); },
+		("trace", "off") => { calc.set_tracer(None); },
+		("trace", arg) => { return Err(format!("Expected \"on\" or \"off\", got \"{}\"", arg)); },
+		_ => { return Err(format!("Unknown command \":{}\"", name)); }
 	}
+	Ok(())
 }
-impl Sub for Value {
-	type Output = Value;
 
-	fn sub(self, rhs: Self) -> Self::Output {
-		match self {
-			Integer(i_left) => {
-				match rhs {
-					Integer(i_right) => {
-						Integer(i_left - i_right)
-					},
-					Float(f_right) => {
-						Float(i_left as f64 - f_right)
-					}
-					Undefined => Undefined
-				}
-			},
-			Float(f_left) => {
-				match rhs {
-					Integer(i_right) => {
-						Float(f_left - (i_right as f64))
-					},
-					Float(f_right) => {
-						Float(f_left - f_right)
-					}
-					Undefined => Undefined
-				}
-			}
-			Undefined => Undefined
-		}
+/// Rewrites `set NAME EXPR` to the assignment `NAME = EXPR`, padded so every token keeps its column for the caret.
+fn expand_set(line: &str) -> Result<Cow<'_, str>, String> {
+	let Some(rest) = line.strip_prefix("set ").map(str::trim_start) else {
+		return Ok(Cow::Borrowed(line));
+	};
+	let (name, expr) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+	// `set` can still be used as a variable, e.g. `set = 3`
+	if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+		return Ok(Cow::Borrowed(line));
 	}
-}
-impl Mul for Value {
-	type Output = Value;
-
-	fn mul(self, rhs: Self) -> Self::Output {
-		match self {
-			Integer(i_left) => {
-				match rhs {
-					Integer(i_right) => {
-						Integer(i_left * i_right)
-					},
-					Float(f_right) => {
-						Float(i_left as f64 * f_right)
-					}
-					Undefined => Undefined
-				}
-			},
-			Float(f_left) => {
-				match rhs {
-					Integer(i_right) => {
-						Float(f_left * (i_right as f64))
-					},
-					Float(f_right) => {
-						Float(f_left * f_right)
-					}
-					Undefined => Undefined
-				}
-			}
-			Undefined => Undefined
-		}
+	// `set x = 7` mixes both forms, the `=` is dropped, `set x += 1` has no meaning
+	let first = Token::tokenize(expr).ok().and_then(|tokens| tokens.into_iter().next());
+	let expr = match first.map(|first| first.token) {
+		Some(Token::Assignment) => Cow::Owned(expr.replacen('=', " ", 1)),
+		Some(Token::CompoundAssignment(_)) => return Err("Usage: set NAME EXPR".to_string()),
+		_ => Cow::Borrowed(expr)
+	};
+	if expr.trim().is_empty() {
+		return Err("Usage: set NAME EXPR".to_string());
 	}
+	let padding = line.chars().count() - rest.chars().count();
+	Ok(Cow::Owned(format!("{}{}={}", " ".repeat(padding), name, expr)))
 }
-impl Div for Value {
-	type Output = Value;
 
-	fn div(self, rhs: Self) -> Self::Output {
-		match self {
-			Integer(i_left) => {
-				match rhs {
-					Integer(i_right) => {
-						Integer(i_left / i_right)
-					},
-					Float(f_right) => {
-						Float(i_left as f64 / f_right)
-					}
-					Undefined => Undefined
-				}
-			},
-			Float(f_left) => {
-				match rhs {
-					Integer(i_right) => {
-						Float(f_left / (i_right as f64))
-					},
-					Float(f_right) => {
-						Float(f_left / f_right)
-					}
-					Undefined => Undefined
-				}
-			}
-			Undefined => Undefined
-		}
-	}
+/// Why a line could not be run.
+enum LineError {
+	/// A `:` command failed.
+	Command(String),
+	/// The expression failed, the offset is the number of characters taken by display keywords in front of it.
+	Eval(Error, usize)
 }
-impl Rem for Value {
-	type Output = Value;
 
-	fn rem(self, rhs: Self) -> Self::Output {
+impl Display for LineError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		match self {
-			Integer(i_left) => {
-				match rhs {
-					Integer(i_right) => {
-						Integer(i_left % i_right)
-					},
-					Float(f_right) => {
-						Float((i_left as f64) % f_right)
-					}
-					Undefined => Undefined
-				}
-			},
-			Float(f_left) => {
-				match rhs {
-					Integer(i_right) => {
-						Float(f_left % (i_right as f64))
-					},
-					Float(f_right) => {
-						Float(f_left % f_right)
-					}
-					Undefined => Undefined
-				}
-			}
-			Undefined => Undefined
+			LineError::Command(msg) => write!(f, "{}", msg),
+			LineError::Eval(error, _) => write!(f, "{}", error)
 		}
 	}
 }
-impl BitAnd for Value {
-	type Output = Result<Value, String>;
 
-	fn bitand(self, rhs: Self) -> Self::Output {
-		match self {
-			Integer(l_int) => {
-				match rhs {
-					Integer(r_int) => {
-						Ok(Integer(l_int & r_int))
-					},
-					Float(_) => {
-						Err(format!("Failed to use operator '{:?}': Bitwise operators are not supported between floating-point values!", BinaryOperatorType::And))
-					},
-					Undefined => Ok(Undefined)
-				}
-			}
-			Float(_) => {
-				Err(format!("Failed to use operator '{:?}': Bitwise operators are not supported between floating-point values!", BinaryOperatorType::And))
-			}
-			Undefined => Ok(Undefined)
-		}
-	}
+/// Calculator state and settings shared by every line of an interactive or batch session.
+#[derive(Default)]
+struct Session {
+	calc: Calculator,
+	settings: Settings,
+	/// Lines that produced the numbered results of the calculator, `inputs[0]` is the line of `$1`.
+	inputs: Vec<String>,
+	/// Prints the number of a result in front of it, e.g. `$3 = 15`, only done at the prompt.
+	numbered: bool
 }
-impl BitOr for Value {
-	type Output = Result<Value, String>;
 
-	fn bitor(self, rhs: Self) -> Self::Output {
-		match self {
-			Integer(l_int) => {
-				match rhs {
-					Integer(r_int) => {
-						Ok(Integer(l_int | r_int))
-					},
-					Float(_) => {
-						Err(format!("Failed to use operator '{:?}': Bitwise operators are not supported between floating-point values!", BinaryOperatorType::Or))
-					},
-					Undefined => Ok(Undefined)
-				}
-			}
-			Float(_) => {
-				Err(format!("Failed to use operator '{:?}': Bitwise operators are not supported between floating-point values!", BinaryOperatorType::Or))
-			}
-			Undefined => Ok(Undefined)
+impl Session {
+	/// Runs a command or a `:` setting, or evaluates a line and prints its results to stdout.
+	/// `input` is the line as typed, error offsets count the whitespace in front of it.
+	fn run_line(&mut self, input: &str) -> Result<(), LineError> {
+		if let Some(command) = input.trim().strip_prefix(':') {
+			return session_command(command, &mut self.calc, &mut self.settings).map_err(LineError::Command);
+		}
+		let mut words = input.split_whitespace();
+		if let Some(command) = words.next().and_then(find_command) {
+			let args: Vec<_> = words.collect();
+			return command.call(self, &args).map_err(LineError::Command);
 		}
-	}
-}
-impl BitXor for Value {
-	type Output = Result<Value, String>;
 
-	fn bitxor(self, rhs: Self) -> Self::Output {
-		match self {
-			Integer(l_int) => {
-				match rhs {
-					Integer(r_int) => {
-						Ok(Integer(l_int ^ r_int))
-					},
-					Float(_) => {
-						Err(format!("Failed to use operator '{:?}': Bitwise operators are not supported between floating-point values!", BinaryOperatorType::Xor))
-					},
-					Undefined => Ok(Undefined)
-				}
-			}
-			Float(_) => {
-				Err(format!("Failed to use operator '{:?}': Bitwise operators are not supported between floating-point values!", BinaryOperatorType::Xor))
-			}
-			Undefined => Ok(Undefined)
+		let mut options = LineOptions { format: self.result_format(), float_info: None, explain: false };
+		// Display keywords can be combined, e.g. `sig 3 group 1 / 7`
+		let mut line = input.trim_start();
+		while let Some(rest) = options.apply_keyword(line, &self.settings) {
+			line = rest;
 		}
-	}
-}
-impl Shl for Value {
-	type Output = Result<Value, String>;
 
-	fn shl(self, rhs: Self) -> Self::Output {
-		match self {
-			Integer(l_int) => {
-				match rhs {
-					Integer(r_int) => {
-						Ok(Integer(l_int << r_int))
-					},
-					Float(_) => {
-						Err(format!("Failed to use operator '{:?}': Bitwise operators are not supported between floating-point values!", BinaryOperatorType::Shl))
-					},
-					Undefined => Ok(Undefined)
-				}
+		// Keywords are stripped from the line, the caret has to be shifted past them
+		let offset = input.chars().count() - line.chars().count();
+		let line = &*expand_set(line).map_err(LineError::Command)?;
+		let values = match options.explain {
+			true => explain::explain(&mut self.calc, line),
+			false => self.calc.calculate_all(line)
+		}.map_err(|error| LineError::Eval(error, offset))?;
+		let mut label = None;
+		if let Some(last) = values.last().filter(|last| !matches!(last, Value::Undefined)) {
+			self.calc.push_result(last.clone());
+			self.inputs.push(input.trim().to_string());
+			label = Some(format!("${} =", self.inputs.len())).filter(|_| self.numbered);
+		}
+		// Definitions have no value, they are skipped
+		let shown: Vec<_> = match self.settings.echo_all {
+			true => values.iter().filter(|value| !matches!(value, Value::Undefined)).collect(),
+			false => values.last().filter(|last| !matches!(last, Value::Undefined)).into_iter().collect()
+		};
+		for (idx, result) in shown.iter().enumerate() {
+			// Only the last value of the line gets a number
+			let label = label.as_ref().filter(|_| idx == shown.len() - 1);
+			match (options.float_info, label) {
+				(Some(_), Some(label)) => println!("{}", label),
+				(None, Some(label)) => print!("{} ", label),
+				_ => {}
 			}
-			Float(_) => {
-				Err(format!("Failed to use operator '{:?}': Bitwise operators are not supported between floating-point values!", BinaryOperatorType::Shl))
+			match options.float_info {
+				Some(32) => println!("{}", FloatInfo::of_f32(result.as_float() as f32)),
+				Some(_) => println!("{}", FloatInfo::of_f64(result.as_float())),
+				None => println!("{}", options.format.format(result))
 			}
-			Undefined => Ok(Undefined)
 		}
+		// A line that ends in a definition shows its header instead
+		let last = line.rsplit(';').next().unwrap_or_default().trim_start();
+		if let (Some(Value::Undefined), Some(def)) = (values.last(), last.strip_prefix("def ")) {
+			println!("[{}]", def.split('=').next().unwrap_or_default().trim());
+		}
+		Ok(())
 	}
-}
-impl Shr for Value {
-	type Output = Result<Value, String>;
-
-	fn shr(self, rhs: Self) -> Self::Output {
-		match self {
-			Integer(l_int) => {
-				match rhs {
-					Integer(r_int) => {
-						Ok(Integer(l_int << r_int))
-					},
-					Float(_) => {
-						Err(format!("Failed to use operator '{:?}': Bitwise operators are not supported between floating-point values!", BinaryOperatorType::Shr))
-					},
-					Undefined => Ok(Undefined)
-				}
-			}
-			Float(_) => {
-				Err(format!("Failed to use operator '{:?}': Bitwise operators are not supported between floating-point values!", BinaryOperatorType::Shr))
-			}
-			Undefined => Ok(Undefined)
+	/// The session format at the current word size.
+	fn result_format(&self) -> NumberFormat {
+		NumberFormat { word_size: self.calc.word_size(), ..self.settings.format }
+	}
+	/// Lists the numbered results with the lines they came from.
+	fn print_history(&self) {
+		if self.inputs.is_empty() {
+			println!("No results yet");
+			return;
 		}
+		let format = self.result_format();
+		let rows: Vec<_> = self.inputs.iter().zip(self.calc.results()).enumerate()
+			.map(|(idx, (input, value))| [format!("${}", idx + 1), input.clone(), format.format(value)])
+			.collect();
+		print_table(["", "Input", "Output"], &rows);
 	}
 }
 
-impl Eq for Value {}
+/// How the result of a single line is shown, set by the display keywords in front of it.
+struct LineOptions {
+	format: NumberFormat,
+	/// Width of the float to break into its fields instead of printing the result.
+	float_info: Option<u32>,
+	/// Prints the steps of the shunting yard before the result.
+	explain: bool
+}
 
-impl PartialEq<Self> for Value {
-	fn eq(&self, other: &Self) -> bool {
-		match self {
-			Undefined => match other {
-				Undefined => true,
-				_ => false
-			},
-			Integer(l_int) => match other {
-				Integer(r_int) => l_int == r_int,
-				_ => false
-			},
-			Float(l_flt) => match other {
-				Integer(r_int) => {
-					*l_flt == (*r_int as f64)
-				},
-				Float(r_flt) => {
-					l_flt == r_flt
-				}
-				Undefined => false
+impl LineOptions {
+	/// Applies the display keyword at the start of `line` and returns the rest, `None` if there is none.
+	fn apply_keyword<'a>(&mut self, line: &'a str, settings: &Settings) -> Option<&'a str> {
+		let (keyword, rest) = line.split_once(' ')?;
+		match keyword {
+			"hex" => self.format.base = NumberBaseType::Hex,
+			"dec" => self.format.base = NumberBaseType::Decimal,
+			"oct" => self.format.base = NumberBaseType::Octal,
+			"bin" => self.format.base = NumberBaseType::Binary,
+			"floatinfo" => self.float_info = Some(64),
+			"floatinfo32" => self.float_info = Some(32),
+			"raw" => self.format.raw_bits = true,
+			"group" => self.format.separator = Some(settings.format.separator.unwrap_or(',')),
+			"explain" => self.explain = true,
+			_ => {
+				let (notation, rest) = split_notation(line)?;
+				self.format.notation = notation;
+				return Some(rest);
 			}
 		}
+		Some(rest.trim_start())
 	}
 }
-impl PartialOrd<Self> for Value {
-	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-		let left = match self {
-			Integer(int) => int.clone() as f64,
-			Float(flt) => flt.clone(),
-			Undefined => f64::NAN
-		};
-		let right = match other {
-			Integer(int) => int.clone() as f64,
-			Float(flt) => flt.clone(),
-			Undefined => f64::NAN
-		};
-		return left.partial_cmp(&right);
+
+/// Reports the error of a line that was not typed at the prompt on stderr.
+/// Lines read in batch mode are located as `<stdin>:line:column`.
+fn report_error(line_number: Option<usize>, line: &str, error: &LineError) {
+	let location = match (line_number, error) {
+		(Some(number), LineError::Eval(error, offset)) => format!("<stdin>:{}:{}: ", number, offset + error.span().start + 1),
+		(Some(number), LineError::Command(_)) => format!("<stdin>:{}: ", number),
+		(None, _) => String::new()
+	};
+	eprintln!("{}Error: {}", location, error);
+	if let LineError::Eval(error, offset) = error {
+		eprintln!("{}", line);
+		eprintln!("{}{}", " ".repeat(*offset), error.underline());
 	}
 }
-impl Ord for Value {
-	fn cmp(&self, other: &Self) -> Ordering {
-		let left = match self {
-			Integer(int) => int.clone() as f64,
-			Float(flt) => flt.clone(),
-			Undefined => f64::NAN
-		};
-		let right = match other {
-			Integer(int) => int.clone() as f64,
-			Float(flt) => flt.clone(),
-			Undefined => f64::NAN
-		};
 
-		if left < right {
-			Ordering::Less
-		} else if left == right {
-			Ordering::Equal
-		} else {
-			Ordering::Greater
+fn main() -> ExitCode {
+	let args: Vec<String> = env::args().skip(1).collect();
+	match args.as_slice() {
+		[] if io::stdin().is_terminal() => {
+			repl();
+			ExitCode::SUCCESS
+		},
+		[] => batch(),
+		[flag] if flag == "--batch" => batch(),
+		[flag, expr] if flag == "-e" => evaluate_once(expr),
+		[command, path] if command == "run" => script::run_file(path),
+		_ => {
+			eprintln!("Usage: ShuntingYardCalculator [run <file> | -e <expression> | --batch]");
+			ExitCode::from(2)
 		}
 	}
 }
 
-impl Display for Value {
-	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-		match self {
-			Undefined => write!(f, "undefined"),
-			Integer(int) => write!(f, "{}", int),
-			Float(flt) => write!(f, "{}", flt)
+fn repl() {
+	let mut editor = match Editor::<ReplHelper, DefaultHistory>::new() {
+		Ok(editor) => editor,
+		Err(err) => {
+			eprintln!("Error: Failed to start the line editor: {}", err);
+			return;
 		}
+	};
+	editor.set_helper(Some(ReplHelper::new()));
+	let history = editor::history_path();
+	if let Some(path) = &history {
+		// There is no history yet on the first run
+		let _ = editor.load_history(path);
 	}
-}
 
-struct Calculator {
-	globals: HashMap<String, Value>
-}
-impl Calculator {
-	fn new() -> Self {
-		Self {
-			globals: HashMap::new()
+	let mut session = Session { numbered: true, ..Session::default() };
+	loop {
+		editor.helper_mut().unwrap().update(&session.calc);
+		// The line is run as typed, so the caret lines up with it
+		let line = match editor.readline(PROMPT) {
+			Ok(line) => line,
+			// Ctrl-C discards the line being edited
+			Err(ReadlineError::Interrupted) => continue,
+			Err(_) => break
+		};
+		if line.trim().is_empty() {
+			continue;
 		}
-	}
-	pub fn set_var(&mut self, name: &String, value: &Value) {
-		if !self.globals.contains_key(name) {
-			self.globals.insert(name.clone(), value.clone());
-		} else {
-			*self.globals.get_mut(name).unwrap() = value.clone();
+		let _ = editor.add_history_entry(line.trim());
+		if line.trim() == "exit" {
+			break;
+		}
+		match session.run_line(&line) {
+			Ok(()) => {},
+			Err(LineError::Eval(error, offset)) => {
+				println!("{}{}", " ".repeat(PROMPT.len() + offset), error.underline().red());
+				println!("{}", format!("Error: {}", error).red())
+			},
+			Err(error) => println!("{}", format!("Error: {}", error).red())
 		}
 	}
-	pub fn calculate(&mut self, src: String) -> Result<Value, String> {
-		let tokens_r = Token::tokenize(src);
-		if let Ok(tokens) = tokens_r {
-			let mut operator_stack = Stack::new();
-			let mut value_queue = Queue::new();
-
-			println!("Tokens: {:?}", tokens);
-
-			for token in &tokens {
-				match token {
-					Token::Integer(_) | Token::Float(_) | Token::Identifier(_) => {
-						value_queue.enqueue(token);
-					},
-					Token::UnaryOperator(op) => {
-						operator_stack.push(token);
-					},
-					Token::BinaryOperator(op) => {
-						if let Some(top) = operator_stack.peek() {
-							if let Token::BinaryOperator(mut op_other) = top {
-								while op_other.order() > op.order() {
-									value_queue.enqueue(operator_stack.pop().unwrap());
-									if let Some(next) = operator_stack.peek() {
-										if let Token::BinaryOperator(op_next) = next {
-											op_other = *op_next;
-										} else {
-											break;
-										}
-									} else {
-										break;
-									}
-								}
-							}
-						}
-						operator_stack.push(token);
-					},
-					Token::OpenParen => {
-						operator_stack.push(token);
-					},
-					Token::CloseParen => {
-						while let Some(op) = operator_stack.pop() {
-							match op {
-								Token::UnaryOperator(_) => { value_queue.enqueue(op) },
-								Token::BinaryOperator(_) => { value_queue.enqueue(op) },
-								Token::OpenParen => { break; },
-								_ => { /* Do nothing */ }
-							}
-						}
-					},
-					_ => { return Err(format!("Unexpected token \"{:?}\"", token)); }
-				}
-			}
-
-			while let Some(op) = operator_stack.pop() {
-				value_queue.enqueue(op);
-			}
-
-			println!("Sorted values: {:?}", value_queue);
-
-			let mut result_stack: Stack<Value> = Stack::new();
-
-			while let Some(token) = value_queue.dequeue() {
-				match token {
-					Token::Integer(int) => {
-						result_stack.push(Integer(*int));
-					},
-					Token::Float(flt) => {
-						result_stack.push(Float(*flt));
-					},
-					Token::Identifier(symbol) => {
-						if let Some(var) = self.globals.get(symbol) {
-							result_stack.push(var.clone());
-						} else {
-							return Err(format!("Variable \"{}\" is undefined!", symbol));
-						}
-					},
-					Token::UnaryOperator(op) => {
-						if !result_stack.is_empty() {
-							let value = result_stack.pop().unwrap();
-							match op {
-								UnaryOperatorType::Negative => {
-									result_stack.push(-value);
-								},
-								UnaryOperatorType::Not => {
-									result_stack.push(Integer(if value.as_float() == 0.0 { 1 } else { 0 }));
-								},
-								UnaryOperatorType::Invert => {
-									match value {
-										Integer(int) => {
-											result_stack.push(Integer(!int));
-										},
-										_ => { return Err("Bitwise operations are only allowed for integer values!".to_string()); }
-									}
-								}
-							}
-						}
-					},
-					Token::BinaryOperator(op) => {
-						if result_stack.len() >= 2 {
-							let right = result_stack.pop().unwrap();
-							let left = result_stack.pop().unwrap();
-							match op {
-								BinaryOperatorType::Add => {
-									result_stack.push(left + right);
-								},
-								BinaryOperatorType::Sub => {
-									result_stack.push(left - right);
-								},
-								BinaryOperatorType::Mul => {
-									result_stack.push(left * right);
-								},
-								BinaryOperatorType::Div => {
-									result_stack.push(left / right);
-								},
-								BinaryOperatorType::Mod => {
-									result_stack.push(left % right);
-								},
-								BinaryOperatorType::Exp => {
-									result_stack.push(left.pow(right));
-								},
-								BinaryOperatorType::Fdiv => {
-									result_stack.push((left / right).floor());
-								},
-								BinaryOperatorType::And => {
-									match left & right {
-										Ok(result) => {
-											result_stack.push(result);
-										},
-										Err(err) => {
-											return Err(err);
-										}
-									}
-								},
-								BinaryOperatorType::Or => {
-									match left | right {
-										Ok(result) => {
-											result_stack.push(result);
-										},
-										Err(err) => {
-											return Err(err);
-										}
-									}
-								},
-								BinaryOperatorType::Xor => {
-									match left ^ right {
-										Ok(result) => {
-											result_stack.push(result);
-										},
-										Err(err) => {
-											return Err(err);
-										}
-									}
-								},
-								BinaryOperatorType::Shl => {
-									match left << right {
-										Ok(result) => {
-											result_stack.push(result);
-										},
-										Err(err) => {
-											return Err(err);
-										}
-									}
-								},
-								BinaryOperatorType::Shr => {
-									match left >> right {
-										Ok(result) => {
-											result_stack.push(result);
-										},
-										Err(err) => {
-											return Err(err);
-										}
-									}
-								},
-								BinaryOperatorType::Less => {
-									result_stack.push(Integer(if left < right { 1 } else { 0 }));
-								},
-								BinaryOperatorType::LessEq => {
-									result_stack.push(Integer(if left <= right { 1 } else { 0 }));
-								},
-								BinaryOperatorType::More => {
-									result_stack.push(Integer(if left > right { 1 } else { 0 }));
-								},
-								BinaryOperatorType::MoreEq => {
-									result_stack.push(Integer(if left >= right { 1 } else { 0 }));
-								},
-								BinaryOperatorType::Equal => {
-									result_stack.push(Integer(if left == right { 1 } else { 0 }));
-								},
-								BinaryOperatorType::NotEqual => {
-									result_stack.push(Integer(if left != right { 1 } else { 0 }));
-								},
-							}
-						} else {
-							return Err(format!("Failed to execute operation {:?}: Not enough values in result stack!", token));
-						}
-					},
-					_ => { return Err(format!("Unexpected token \"{:?}\"", token)); }
-				}
-			}
-
-			if let Some(result) = result_stack.pop() {
-				Ok(result)
-			} else {
-				Err("No value in result stack!".to_string())
-			}
-		} else {
-			Err(tokens_r.err().unwrap())
+	if let Some(path) = &history {
+		if let Err(err) = editor.save_history(path) {
+			eprintln!("Error: Failed to save the history to \"{}\": {}", path.display(), err);
 		}
 	}
 }
 
-fn prompt(message: &'static str) -> String {
-	print!("{}", message);
-	io::stdout().flush().unwrap();
-	let mut line = String::new();
-	io::stdin().lock().read_line(&mut line).unwrap();
-	return line.trim().to_string();
+/// Evaluates the line given with `-e` and exits, the exit code is non-zero if it failed.
+fn evaluate_once(line: &str) -> ExitCode {
+	match Session::default().run_line(line) {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(error) => {
+			report_error(None, line, &error);
+			ExitCode::FAILURE
+		}
+	}
 }
 
-fn main() {
-	let mut calc = Calculator::new();
-	let mut line = prompt(">>> ");
-	let mut assign = String::new();
-	let mut display_as = NumberBaseType::Decimal;
-
-	while line != "exit" {
-		if line.starts_with("set ") {
-			line = line.replace("set ", "");
-			let c = line.chars().nth(0).unwrap();
-			if !c.is_whitespace() && c.is_alphanumeric() || c == '_' {
-				assign.push(c);
-				line.remove(0);
-			}
-		} else if line.starts_with("hex ") {
-			display_as = NumberBaseType::Hex;
-			line = line.replace("hex ", "");
-		} else if line.starts_with("dec ") {
-			display_as = NumberBaseType::Decimal;
-			line = line.replace("dec ", "");
-		} else if line.starts_with("oct ") {
-			display_as = NumberBaseType::Octal;
-			line = line.replace("oct ", "");
-		} else if line.starts_with("bin ") {
-			display_as = NumberBaseType::Binary;
-			line = line.replace("bin ", "");
+/// Runs every line of stdin without prompting, until `exit` or the end of the input.
+/// Errors don't stop the batch, but make the exit code non-zero.
+fn batch() -> ExitCode {
+	let mut session = Session::default();
+	let mut status = ExitCode::SUCCESS;
+	for (idx, line) in io::stdin().lock().lines().enumerate() {
+		let Ok(line) = line else {
+			break;
+		};
+		if line.trim() == "exit" {
+			break;
 		}
-
-		match calc.calculate(line) {
-			Ok(result) => {
-				if assign.is_empty() {
-					match display_as {
-						NumberBaseType::Decimal => { println!("{}", result); },
-						NumberBaseType::Binary => {
-							match result {
-								Undefined => { println!("undefined"); }
-								Integer(int) => { println!("{:#b}", int); }
-								Float(flt) => { println!("{}", flt); }
-							}
-						},
-						NumberBaseType::Octal => {
-							match result {
-								Undefined => { println!("undefined"); }
-								Integer(int) => { println!("{:#o}", int); }
-								Float(flt) => { println!("{}", flt); }
-							}
-						},
-						NumberBaseType::Hex => {
-							match result {
-								Undefined => { println!("undefined"); }
-								Integer(int) => { println!("{:#X}", int); }
-								Float(flt) => { println!("{}", flt); }
-							}
-						},
-					}
-				} else {
-					calc.set_var(&assign, &result);
-					println!("[{}]: {}", assign, result);
-				}
-			},
-			Err(error) => {
-				println!("{}", format!("Error: {}", error).red())
-			}
+		// Blank lines and comments have no tokens
+		if Token::tokenize(&line).is_ok_and(|tokens| tokens.is_empty()) {
+			continue;
+		}
+		// The line is run and echoed as read, so the column counts the whitespace in front of it
+		if let Err(error) = session.run_line(&line) {
+			report_error(Some(idx + 1), &line, &error);
+			status = ExitCode::FAILURE;
 		}
-
-		assign.clear();
-		display_as = NumberBaseType::Decimal;
-		line = prompt(">>> ");
 	}
+	status
 }
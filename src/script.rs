@@ -0,0 +1,61 @@
+use std::fs;
+use std::process::ExitCode;
+use shunting_yard_calculator::{Calculator, Error, NumberFormat, Token, Value};
+use shunting_yard_calculator::token::SpannedToken;
+
+/// Runs a script file line by line, printing the value of every line that is not an assignment or definition.
+/// Stops at the first error, which is reported as `file:line:column`.
+pub fn run_file(path: &str) -> ExitCode {
+	let src = match fs::read_to_string(path) {
+		Ok(src) => src,
+		Err(err) => {
+			eprintln!("Error: Failed to read \"{}\": {}", path, err);
+			return ExitCode::from(2);
+		}
+	};
+
+	let mut calc = Calculator::new();
+	let format = NumberFormat::default();
+	let result = run_lines(&mut calc, &src, |line, value| {
+		if !is_silent(line) {
+			println!("{}", format.format(&value));
+		}
+	});
+	match result {
+		Ok(()) => ExitCode::SUCCESS,
+		Err((number, line, error)) => {
+			eprintln!("{}:{}:{}: Error: {}", path, number, error.span().start + 1, error);
+			eprintln!("{}", line);
+			eprintln!("{}", error.underline());
+			ExitCode::FAILURE
+		}
+	}
+}
+
+/// Evaluates the lines of a script in order and passes the value of every line to `on_value`.
+/// Stops at the first error, which is returned with the number and text of its line.
+pub fn run_lines<'a>(calc: &mut Calculator, src: &'a str, mut on_value: impl FnMut(&str, Value)) -> Result<(), (usize, &'a str, Error)> {
+	for (idx, line) in src.lines().enumerate() {
+		// Blank lines and comments have no tokens
+		if Token::tokenize(line).is_ok_and(|tokens| tokens.is_empty()) {
+			continue;
+		}
+		match calc.calculate(line) {
+			Ok(value) => on_value(line, value),
+			Err(error) => { return Err((idx + 1, line, error)); }
+		}
+	}
+	Ok(())
+}
+
+/// Whether the last statement of the line is an assignment or a function definition.
+fn is_silent(line: &str) -> bool {
+	let Ok(tokens) = Token::tokenize(line) else {
+		return false;
+	};
+	match tokens.split(|spanned| spanned.token == Token::Semicolon).rfind(|statement| !statement.is_empty()) {
+		Some([SpannedToken { token: Token::Identifier(keyword), .. }, ..]) if keyword == "def" => true,
+		Some([_, SpannedToken { token: Token::Assignment | Token::CompoundAssignment(_), .. }, ..]) => true,
+		_ => false
+	}
+}
@@ -0,0 +1,17 @@
+/// Prints rows in columns as wide as their longest cell.
+pub fn print_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) {
+	let mut widths = header.map(|title| title.chars().count());
+	for row in rows {
+		for (width, cell) in widths.iter_mut().zip(row) {
+			*width = (*width).max(cell.chars().count());
+		}
+	}
+	let print_row = |cells: [&str; N]| {
+		let line: Vec<_> = cells.iter().zip(widths).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect();
+		println!("{}", line.join("  ").trim_end());
+	};
+	print_row(header);
+	for row in rows {
+		print_row(row.each_ref().map(String::as_str));
+	}
+}
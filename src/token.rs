@@ -1,4 +1,8 @@
-use crate::Value;
+use std::fmt::{Display, Formatter};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use crate::error::{Error, Span};
+use crate::value::Value;
 
 pub trait AsLowerCase {
 	fn as_lowercase(&self) -> char;
@@ -9,16 +13,23 @@ pub trait AsUpperCase {
 impl AsLowerCase for char {
 	fn as_lowercase(&self) -> char {
 		let result: Vec<_> = self.to_lowercase().collect();
-		return *result.first().unwrap();
+		*result.first().unwrap()
 	}
 }
 impl AsUpperCase for char {
 	fn as_uppercase(&self) -> char {
 		let result: Vec<_> = self.to_uppercase().collect();
-		return *result.first().unwrap();
+		*result.first().unwrap()
 	}
 }
 
+/// Which side a chain of operators with the same precedence groups from.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Associativity {
+	Left,
+	Right
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum UnaryOperatorType {
 	Negative,
@@ -26,6 +37,20 @@ pub enum UnaryOperatorType {
 	Invert
 }
 
+impl UnaryOperatorType {
+	/// Prefix operators bind tighter than everything except `**`, so `-2 ** 2` is `-(2 ** 2)`.
+	pub fn order(self) -> u32 {
+		7
+	}
+	pub fn symbol(self) -> &'static str {
+		match self {
+			UnaryOperatorType::Negative => "-",
+			UnaryOperatorType::Not => "!",
+			UnaryOperatorType::Invert => "~"
+		}
+	}
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum BinaryOperatorType {
 	Add,
@@ -48,6 +73,28 @@ pub enum BinaryOperatorType {
 	NotEqual
 }
 impl BinaryOperatorType {
+	pub fn symbol(self) -> &'static str {
+		match self {
+			BinaryOperatorType::Add => "+",
+			BinaryOperatorType::Sub => "-",
+			BinaryOperatorType::Mul => "*",
+			BinaryOperatorType::Div => "/",
+			BinaryOperatorType::Mod => "%",
+			BinaryOperatorType::Exp => "**",
+			BinaryOperatorType::Fdiv => "//",
+			BinaryOperatorType::And => "&",
+			BinaryOperatorType::Or => "|",
+			BinaryOperatorType::Xor => "^",
+			BinaryOperatorType::Shl => "<<",
+			BinaryOperatorType::Shr => ">>",
+			BinaryOperatorType::Less => "<",
+			BinaryOperatorType::LessEq => "<=",
+			BinaryOperatorType::More => ">",
+			BinaryOperatorType::MoreEq => ">=",
+			BinaryOperatorType::Equal => "==",
+			BinaryOperatorType::NotEqual => "!="
+		}
+	}
 	pub fn order(self) -> u32 {
 		match self {
 			BinaryOperatorType::Add => 5,
@@ -55,8 +102,8 @@ impl BinaryOperatorType {
 			BinaryOperatorType::Mul => 6,
 			BinaryOperatorType::Div => 6,
 			BinaryOperatorType::Mod => 6,
-			BinaryOperatorType::Exp => 7,
-			BinaryOperatorType::Fdiv => 5,
+			BinaryOperatorType::Exp => 8,
+			BinaryOperatorType::Fdiv => 6,
 			BinaryOperatorType::And => 2,
 			BinaryOperatorType::Or => 0,
 			BinaryOperatorType::Xor => 1,
@@ -70,33 +117,90 @@ impl BinaryOperatorType {
 			BinaryOperatorType::NotEqual => 3
 		}
 	}
+	/// Every operator except the comparisons, the ones that have a compound assignment.
+	pub fn is_arithmetic(self) -> bool {
+		!matches!(self, BinaryOperatorType::Less | BinaryOperatorType::LessEq | BinaryOperatorType::More
+			| BinaryOperatorType::MoreEq | BinaryOperatorType::Equal | BinaryOperatorType::NotEqual)
+	}
+	pub fn associativity(self) -> Associativity {
+		match self {
+			BinaryOperatorType::Exp => Associativity::Right,
+			_ => Associativity::Left
+		}
+	}
 }
 
 #[derive(Debug)]
 pub enum Token {
 	Invalid,
 	Integer(i64),
+	/// Integer literal too large for `i64`.
+	BigInteger(BigInt),
 	Float(f64),
 	Identifier(String),
 	UnaryOperator(UnaryOperatorType),
 	BinaryOperator(BinaryOperatorType),
 	Assignment,
+	/// Assignment combined with an operator, e.g. `+=`.
+	CompoundAssignment(BinaryOperatorType),
 	OpenParen,
-	CloseParen
+	CloseParen,
+	Comma,
+	/// Separates statements on one line.
+	Semicolon,
+	/// Identifier directly followed by an opening parenthesis.
+	Function(String),
+	/// Function call with its number of arguments, only produced by the shunting yard.
+	Call(String, usize),
+	/// Assignment to the named variable, combined with the operator of a compound assignment.
+	/// Only produced by the shunting yard.
+	Store(String, Option<BinaryOperatorType>)
 }
 
 impl Clone for Token {
 	fn clone(&self) -> Self {
 		match self {
 			Token::Invalid => Token::Invalid,
-			Token::Integer(i) => Token::Integer(i.clone()),
-			Token::Float(f) => Token::Float(f.clone()),
+			Token::Integer(i) => Token::Integer(*i),
+			Token::BigInteger(big) => Token::BigInteger(big.clone()),
+			Token::Float(f) => Token::Float(*f),
 			Token::Identifier(id) => Token::Identifier(id.clone()),
-			Token::UnaryOperator(u) => Token::UnaryOperator(u.clone()),
-			Token::BinaryOperator(b) => Token::BinaryOperator(b.clone()),
+			Token::UnaryOperator(u) => Token::UnaryOperator(*u),
+			Token::BinaryOperator(b) => Token::BinaryOperator(*b),
 			Token::Assignment => Token::Assignment,
+			Token::CompoundAssignment(op) => Token::CompoundAssignment(*op),
 			Token::OpenParen => Token::OpenParen,
 			Token::CloseParen => Token::CloseParen,
+			Token::Comma => Token::Comma,
+			Token::Semicolon => Token::Semicolon,
+			Token::Function(name) => Token::Function(name.clone()),
+			Token::Call(name, argc) => Token::Call(name.clone(), *argc),
+			Token::Store(name, op) => Token::Store(name.clone(), *op),
+		}
+	}
+}
+
+/// Prints the token the way it is written in the source, calls as `name/argc` and stores as `name=`.
+impl Display for Token {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Token::Invalid => write!(f, "?"),
+			Token::Integer(i) => write!(f, "{}", i),
+			Token::BigInteger(big) => write!(f, "{}", big),
+			Token::Float(flt) => write!(f, "{:?}", flt),
+			Token::Identifier(id) => write!(f, "{}", id),
+			Token::UnaryOperator(op) => write!(f, "{}", op.symbol()),
+			Token::BinaryOperator(op) => write!(f, "{}", op.symbol()),
+			Token::Assignment => write!(f, "="),
+			Token::CompoundAssignment(op) => write!(f, "{}=", op.symbol()),
+			Token::OpenParen => write!(f, "("),
+			Token::CloseParen => write!(f, ")"),
+			Token::Comma => write!(f, ","),
+			Token::Semicolon => write!(f, ";"),
+			Token::Function(name) => write!(f, "{}", name),
+			Token::Call(name, argc) => write!(f, "{}/{}", name, argc),
+			Token::Store(name, None) => write!(f, "{}=", name),
+			Token::Store(name, Some(op)) => write!(f, "{}{}=", name, op.symbol())
 		}
 	}
 }
@@ -104,60 +208,22 @@ impl Clone for Token {
 impl PartialEq<Self> for Token {
 	fn eq(&self, other: &Self) -> bool {
 		match self {
-			Token::Invalid => {
-				match other {
-					Token::Invalid => true,
-					_ => false
-				}
-			},
-			Token::Integer(_) => {
-				match other {
-					Token::Integer(_) => true,
-					_ => false
-				}
-			},
-			Token::Float(_) => {
-				match other {
-					Token::Float(_) => true,
-					_ => false
-				}
-			},
-			Token::Identifier(_) => {
-				match other {
-					Token::Identifier(_) => true,
-					_ => false
-				}
-			},
-			Token::UnaryOperator(_) => {
-				match other {
-					Token::UnaryOperator(_) => true,
-					_ => false
-				}
-			},
-			Token::BinaryOperator(_) => {
-				match other {
-					Token::BinaryOperator(_) => true,
-					_ => false
-				}
-			},
-			Token::Assignment => {
-				match other {
-					Token::Assignment => true,
-					_ => false
-				}
-			}
-			Token::OpenParen => {
-				match other {
-					Token::OpenParen => true,
-					_ => false
-				}
-			},
-			Token::CloseParen => {
-				match other {
-					Token::CloseParen => true,
-					_ => false
-				}
-			}
+			Token::Invalid => matches!(other, Token::Invalid),
+			Token::Integer(_) => matches!(other, Token::Integer(_)),
+			Token::BigInteger(_) => matches!(other, Token::BigInteger(_)),
+			Token::Float(_) => matches!(other, Token::Float(_)),
+			Token::Identifier(_) => matches!(other, Token::Identifier(_)),
+			Token::UnaryOperator(_) => matches!(other, Token::UnaryOperator(_)),
+			Token::BinaryOperator(_) => matches!(other, Token::BinaryOperator(_)),
+			Token::Assignment => matches!(other, Token::Assignment),
+			Token::CompoundAssignment(_) => matches!(other, Token::CompoundAssignment(_)),
+			Token::OpenParen => matches!(other, Token::OpenParen),
+			Token::CloseParen => matches!(other, Token::CloseParen),
+			Token::Comma => matches!(other, Token::Comma),
+			Token::Semicolon => matches!(other, Token::Semicolon),
+			Token::Function(_) => matches!(other, Token::Function(_)),
+			Token::Call(..) => matches!(other, Token::Call(..)),
+			Token::Store(..) => matches!(other, Token::Store(..))
 		}
 	}
 }
@@ -172,8 +238,9 @@ pub enum TokenizerState {
 	BinaryOperator
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
 pub enum NumberBaseType {
+	#[default]
 	Decimal,
 	Binary,
 	Octal,
@@ -183,17 +250,17 @@ impl NumberBaseType {
 	fn is_char_valid(self, c: char) -> bool {
 		match self {
 			NumberBaseType::Decimal => {
-				c >= '0' && c <= '9'
+				c.is_ascii_digit()
 			},
 			NumberBaseType::Binary => {
 				c == '0' || c == '1'
 			},
 			NumberBaseType::Octal => {
-				c >= '0' && c <= '7'
+				('0'..='7').contains(&c)
 			},
 			NumberBaseType::Hex => {
 				let clow = c.as_lowercase();
-				(c >= '0' && c <= '9') || (clow >= 'a' && clow <= 'f')
+				c.is_ascii_digit() || ('a'..='f').contains(&clow)
 			}
 		}
 	}
@@ -201,350 +268,307 @@ impl NumberBaseType {
 
 impl Token {
 	pub fn is_operator(&self) -> bool {
-		match self {
-			Token::UnaryOperator(_) => true,
-			Token::BinaryOperator(_) => true,
-			_ => false
-		}
+		matches!(self, Token::UnaryOperator(_) | Token::BinaryOperator(_))
 	}
 	pub fn is_unary_operator(&self) -> bool {
-		match self {
-			Token::UnaryOperator(_) => true,
-			_ => false
-		}
+		matches!(self, Token::UnaryOperator(_))
 	}
 	pub fn is_binary_operator(&self) -> bool {
-		match self {
-			Token::BinaryOperator(_) => true,
-			_ => false
-		}
+		matches!(self, Token::BinaryOperator(_))
 	}
 	pub fn is_value(&self) -> bool {
-		match self {
-			Token::Integer(_) | Token::Float(_) | Token::Identifier(_) => true,
-			_ => false
-		}
+		matches!(self, Token::Integer(_) | Token::BigInteger(_) | Token::Float(_) | Token::Identifier(_))
 	}
 	pub fn is_identifier(&self) -> bool {
-		match self {
-			Token::Identifier(_) => true,
-			_ => false
-		}
+		matches!(self, Token::Identifier(_))
 	}
 	pub fn as_value(&self) -> Option<Value> {
 		match self {
-			Token::Integer(int) => Some(Value::Integer(int.clone())),
-			Token::Float(flt) => Some(Value::Float(flt.clone())),
+			Token::Integer(int) => Some(Value::Integer(*int)),
+			Token::BigInteger(big) => Some(Value::from(big.clone())),
+			Token::Float(flt) => Some(Value::Float(*flt)),
 			_ => None
 		}
 	}
 	fn char_is_operator(c: char) -> bool {
 		"+-*/%&|^<>=".contains(c)
 	}
-	pub fn tokenize(src: String) -> Result<Vec<Token>, String> {
-		let mut tokens = Vec::new();
+	fn char_ends_token(c: char) -> bool {
+		Token::char_is_operator(c) || c.is_whitespace() || "(),;#".contains(c)
+	}
+	/// Parses the digits of a literal, `source` is the literal as written and quoted in errors.
+	fn parse_number(literal: &str, source: &str, base: NumberBaseType, span: Span) -> Result<Token, Error> {
+		let radix = match base {
+			NumberBaseType::Decimal => {
+				if literal.contains(['.', 'e']) {
+					return match literal.parse::<f64>() {
+						Ok(flt) if flt.is_infinite() => Err(Error::Lex(format!("Number literal \"{}\" is out of range", source), span)),
+						Ok(flt) => Ok(Token::Float(flt)),
+						Err(err) => Err(Error::Lex(format!("Failed to parse number literal \"{}\": {}", source, err), span))
+					};
+				}
+				10
+			},
+			NumberBaseType::Binary => 2,
+			NumberBaseType::Octal => 8,
+			NumberBaseType::Hex => 16
+		};
+		match BigInt::parse_bytes(literal.as_bytes(), radix) {
+			Some(big) => {
+				match big.to_i64() {
+					Some(int) => Ok(Token::Integer(int)),
+					None => Ok(Token::BigInteger(big))
+				}
+			},
+			None => Err(Error::Lex(format!("Failed to parse number literal \"{}\"", source), span))
+		}
+	}
+	pub fn tokenize(src: &str) -> Result<Vec<SpannedToken>, Error> {
+		let chars: Vec<char> = src.chars().collect();
+		let mut tokens: Vec<SpannedToken> = Vec::new();
 		let mut i = 0usize;
+		let mut start = 0usize;
 		let mut token_value = String::new();
 		let mut found_decimal = false;
+		let mut found_exponent = false;
 		let mut number_base = NumberBaseType::Decimal;
 		let mut state = TokenizerState::Default;
-		let mut should_parse = false;
 
-		while i < src.len() {
-			match src.chars().nth(i) {
-				Some(mut c) => {
-					match state {
-						TokenizerState::Default => {
-							if c.is_whitespace() {
-								i += 1;
-							} else if c.is_alphabetic() || c == '_' {
-								state = TokenizerState::Identifier;
-							} else if c.is_numeric() {
-								if c == '0' {
+		loop {
+			let current = chars.get(i).copied();
+			match state {
+				TokenizerState::Default => {
+					let Some(c) = current else { break; };
+					start = i;
+					if c.is_whitespace() {
+						i += 1;
+					} else if c.is_alphabetic() || c == '_' {
+						token_value.clear();
+						state = TokenizerState::Identifier;
+					} else if c.is_ascii_digit() || c == '.' && chars.get(i + 1).is_some_and(|next| next.is_ascii_digit()) {
+						found_decimal = false;
+						found_exponent = false;
+						token_value.clear();
+						number_base = NumberBaseType::Decimal;
+						if c == '0' {
+							match chars.get(i + 1).map(|next| next.as_lowercase()) {
+								Some('x') => {
+									number_base = NumberBaseType::Hex;
+									i += 2;
+								},
+								Some('b') => {
+									number_base = NumberBaseType::Binary;
+									i += 2;
+								},
+								Some('o') => {
+									number_base = NumberBaseType::Octal;
+									i += 2;
+								},
+								Some(next) if next.is_ascii_digit() => {
+									number_base = NumberBaseType::Octal;
 									i += 1;
-									let cr = src.chars().nth(i);
-									if cr.is_none() {
-										tokens.push(Token::Integer(0));
-										break;
-									}
-									c = cr.unwrap().as_lowercase();
-									if c == 'x' {
-										number_base = NumberBaseType::Hex;
-										i += 1;
-									} else if c == 'b' {
-										number_base = NumberBaseType::Binary;
-										i += 1;
-									} else if c == 'o' || c.is_numeric() {
-										number_base = NumberBaseType::Octal;
-										if c == 'o' {
-											i += 1;
-										}
-									} else {
-										number_base = NumberBaseType::Decimal;
-									}
-								} else {
-									number_base = NumberBaseType::Decimal;
-								}
-								found_decimal = false;
-								token_value.clear();
-								state = TokenizerState::Number;
-							} else if "-!~".contains(c) {
-								if c == '-' && (tokens.len() > 0 && !tokens.last().unwrap().is_binary_operator()) {
-									state = TokenizerState::BinaryOperator;
-								} else if c == '!' {
+								},
+								_ => { /* Plain zero, parsed as decimal */ }
+							}
+						}
+						state = TokenizerState::Number;
+					} else if "-!~".contains(c) {
+						if c == '-' && tokens.last().is_some_and(|last| last.token.is_value() || last.token == Token::CloseParen) {
+							state = TokenizerState::BinaryOperator;
+						} else if c == '!' {
+							match chars.get(i + 1) {
+								Some('=') => {
+									tokens.push(SpannedToken::new(Token::BinaryOperator(BinaryOperatorType::NotEqual), Span::new(i, i + 2)));
+									i += 2;
+								},
+								Some(_) => {
+									tokens.push(SpannedToken::new(Token::UnaryOperator(UnaryOperatorType::Not), Span::new(i, i + 1)));
 									i += 1;
-									let cr = src.chars().nth(i);
-									if cr.is_none() {
-										return Err(format!("Unexpected token {}", c));
-									}
-
-									c = cr.unwrap();
-									if c == '=' {
-										tokens.push(Token::BinaryOperator(BinaryOperatorType::NotEqual));
-										i += 1;
-									} else {
-										tokens.push(Token::UnaryOperator(UnaryOperatorType::Not));
-									}
-								} else {
-									match c {
-										'-' => { tokens.push(Token::UnaryOperator(UnaryOperatorType::Negative)) },
-										'!' => { tokens.push(Token::UnaryOperator(UnaryOperatorType::Not)) },
-										'~' => { tokens.push(Token::UnaryOperator(UnaryOperatorType::Invert)) },
-										_ => { return Err(format!("Invalid unary operator '{}'", c)) }
-									}
-									i += 1
+								},
+								None => {
+									return Err(Error::Lex(format!("Unexpected token {}", c), Span::new(i, i + 1)));
 								}
-							} else if Token::char_is_operator(c) {
-								state = TokenizerState::BinaryOperator;
-							} else if c == '(' {
-								tokens.push(Token::OpenParen);
-								i += 1;
-							} else if c == ')' {
-								tokens.push(Token::CloseParen);
-								i += 1;
-							} else {
-								return Err(format!("Unexpected character '{}'", c));
 							}
+						} else {
+							let op = match c {
+								'-' => UnaryOperatorType::Negative,
+								_ => UnaryOperatorType::Invert
+							};
+							tokens.push(SpannedToken::new(Token::UnaryOperator(op), Span::new(i, i + 1)));
+							i += 1;
+						}
+					} else if Token::char_is_operator(c) {
+						state = TokenizerState::BinaryOperator;
+					} else if c == '(' {
+						tokens.push(SpannedToken::new(Token::OpenParen, Span::new(i, i + 1)));
+						i += 1;
+					} else if c == ')' {
+						tokens.push(SpannedToken::new(Token::CloseParen, Span::new(i, i + 1)));
+						i += 1;
+					} else if c == ',' {
+						tokens.push(SpannedToken::new(Token::Comma, Span::new(i, i + 1)));
+						i += 1;
+					} else if c == ';' {
+						tokens.push(SpannedToken::new(Token::Semicolon, Span::new(i, i + 1)));
+						i += 1;
+					} else if c == '$' {
+						// `$3` reads the result of the third line back, it is named like a variable
+						let digits = chars[i + 1..].iter().take_while(|c| c.is_ascii_digit()).count();
+						if digits == 0 {
+							return Err(Error::Lex("Expected the number of a result after '$'".to_string(), Span::new(i, i + 1)));
+						}
+						let number: String = chars[i + 1..i + 1 + digits].iter().collect();
+						tokens.push(SpannedToken::new(Token::Identifier(format!("${}", number)), Span::new(i, i + 1 + digits)));
+						i += 1 + digits;
+					} else if c == '#' {
+						// Comments run until the end of the line
+						while chars.get(i).is_some_and(|c| *c != '\n') {
+							i += 1;
+						}
+					} else {
+						return Err(Error::Lex(format!("Unexpected character '{}'", c), Span::new(i, i + 1)));
+					}
+				},
+				TokenizerState::Number => {
+					// Errors quote the literal as written, with its prefix and underscores
+					let source = |end: usize| chars[start..end].iter().collect::<String>();
+					match current {
+						Some('_') => {
+							i += 1;
 						},
-						TokenizerState::Number => {
-							if c == '_' {
-								i += 1;
-							} else if number_base == NumberBaseType::Decimal && c == '.' {
-								if found_decimal {
-									return Err(format!("Invalid number literal \"{}{}\"", token_value, c));
-								} else {
-									token_value.push(c);
-									found_decimal = true;
-									i += 1;
-								}
-							} else if number_base.is_char_valid(c) {
-								token_value.push(c);
-								i += 1;
-							} else if Token::char_is_operator(c) || c.is_whitespace() || c == '(' || c == ')' {
-								should_parse = true;
-							} else {
-								return Err(format!("Invalid number literal \"{}{}\"", token_value, c));
+						Some('.') if number_base == NumberBaseType::Decimal => {
+							if found_exponent {
+								return Err(Error::Lex(format!("Decimal point in the exponent of number literal \"{}\"", source(i + 1)), Span::new(i, i + 1)));
 							}
-
-							if i + 1 >= src.len() || should_parse {
-								match number_base {
-									NumberBaseType::Decimal => {
-										if token_value.contains('.') {
-											match token_value.parse::<f64>() {
-												Ok(f) => {
-													tokens.push(Token::Float(f))
-												},
-												Err(err) => {
-													return Err(format!("Failed to parse number literal: \"{:?}\"", err));
-												}
-											}
-										} else {
-											match token_value.parse::<i64>() {
-												Ok(int) => {
-													tokens.push(Token::Integer(int))
-												},
-												Err(err) => {
-													return Err(format!("Failed to parse number literal: \"{:?}\"", err));
-												}
-											}
-										}
-									}
-									NumberBaseType::Binary => {
-										match i64::from_str_radix(token_value.as_str(), 2) {
-											Ok(int) => {
-												tokens.push(Token::Integer(int))
-											},
-											Err(err) => {
-												return Err(format!("Failed to parse number literal: \"{:?}\"", err));
-											}
-										}
-									},
-									NumberBaseType::Octal => {
-										match i64::from_str_radix(token_value.as_str(), 8) {
-											Ok(int) => {
-												tokens.push(Token::Integer(int))
-											},
-											Err(err) => {
-												return Err(format!("Failed to parse number literal: \"{:?}\"", err));
-											}
-										}
-									},
-									NumberBaseType::Hex => {
-										match i64::from_str_radix(token_value.as_str(), 16) {
-											Ok(int) => {
-												tokens.push(Token::Integer(int))
-											},
-											Err(err) => {
-												return Err(format!("Failed to parse number literal: \"{:?}\"", err));
-											}
-										}
-									}
-								}
-								should_parse = false;
-								found_decimal = false;
-								token_value.clear();
-								state = TokenizerState::Default;
+							if found_decimal {
+								return Err(Error::Lex(format!("Second decimal point in number literal \"{}\"", source(i + 1)), Span::new(i, i + 1)));
 							}
+							token_value.push('.');
+							found_decimal = true;
+							i += 1;
 						},
-						TokenizerState::Identifier => {
-							if Token::char_is_operator(c) || c.is_whitespace() || c == '(' || c == ')' || i + 1 >= src.len() {
-								tokens.push(Token::Identifier(token_value.clone()));
-								token_value.clear();
-								state = TokenizerState::Default;
-							} else if c.is_alphanumeric() || c == '_' {
-								token_value.push(c);
+						Some('e' | 'E') if number_base == NumberBaseType::Decimal => {
+							if found_exponent {
+								return Err(Error::Lex(format!("Second exponent in number literal \"{}\"", source(i + 1)), Span::new(i, i + 1)));
+							}
+							token_value.push('e');
+							found_exponent = true;
+							i += 1;
+							// The exponent may have a sign, which would otherwise be read as an operator
+							if let Some(sign @ ('+' | '-')) = chars.get(i) {
+								token_value.push(*sign);
 								i += 1;
-							} else {
-								return Err(format!("Unexpected character '{}'", c));
+							}
+							if !chars.get(i).is_some_and(|next| next.is_ascii_digit()) {
+								return Err(Error::Lex(format!("Missing digits in the exponent of number literal \"{}\"", source(i)), Span::new(start, i)));
 							}
 						},
-						TokenizerState::BinaryOperator => {
-							match c {
-								'+' => {
-									tokens.push(Token::BinaryOperator(BinaryOperatorType::Add));
-									state = TokenizerState::Default;
-									i += 1;
-								},
-								'-' => {
-									tokens.push(Token::BinaryOperator(BinaryOperatorType::Sub));
-									state = TokenizerState::Default;
-									i += 1;
-								},
-								'*' => {
-									i += 1;
-									let cr = src.chars().nth(i);
-									if cr.is_some() {
-										c = cr.unwrap();
-										if c == '*' {
-											tokens.push(Token::BinaryOperator(BinaryOperatorType::Exp));
-											i += 1;
-											state = TokenizerState::Default;
-											continue;
-										}
-									}
-									tokens.push(Token::BinaryOperator(BinaryOperatorType::Mul));
-									state = TokenizerState::Default;
-								},
-								'/' => {
-									i += 1;
-									let cr = src.chars().nth(i);
-									if cr.is_some() {
-										c = cr.unwrap();
-										if c == '/' {
-											tokens.push(Token::BinaryOperator(BinaryOperatorType::Fdiv));
-											i += 1;
-											state = TokenizerState::Default;
-											continue;
-										}
-									}
-									tokens.push(Token::BinaryOperator(BinaryOperatorType::Div));
-									state = TokenizerState::Default;
-								},
-								'%' => {
-									tokens.push(Token::BinaryOperator(BinaryOperatorType::Mod));
-									state = TokenizerState::Default;
-									i += 1;
-								},
-								'&' => {
-									tokens.push(Token::BinaryOperator(BinaryOperatorType::And));
-									state = TokenizerState::Default;
-									i += 1;
-								},
-								'|' => {
-									tokens.push(Token::BinaryOperator(BinaryOperatorType::Or));
-									state = TokenizerState::Default;
-									i += 1;
-								},
-								'^' => {
-									tokens.push(Token::BinaryOperator(BinaryOperatorType::Xor));
-									state = TokenizerState::Default;
-									i += 1;
-								},
-								'<' => {
-									i += 1;
-									let cr = src.chars().nth(i);
-									if cr.is_some() {
-										c = cr.unwrap();
-										if c == '<' {
-											tokens.push(Token::BinaryOperator(BinaryOperatorType::Shl));
-											i += 1;
-											state = TokenizerState::Default;
-											continue;
-										} else if c == '=' {
-											tokens.push(Token::BinaryOperator(BinaryOperatorType::LessEq));
-											i += 1;
-											state = TokenizerState::Default;
-											continue;
-										}
-									}
-									tokens.push(Token::BinaryOperator(BinaryOperatorType::Less));
-									state = TokenizerState::Default;
-								},
-								'>' => {
-									i += 1;
-									let cr = src.chars().nth(i);
-									if cr.is_some() {
-										c = cr.unwrap();
-										if c == '>' {
-											tokens.push(Token::BinaryOperator(BinaryOperatorType::Shr));
-											i += 1;
-											state = TokenizerState::Default;
-											continue;
-										} else if c == '=' {
-											tokens.push(Token::BinaryOperator(BinaryOperatorType::MoreEq));
-											i += 1;
-											state = TokenizerState::Default;
-											continue;
-										}
-									}
-									tokens.push(Token::BinaryOperator(BinaryOperatorType::More));
-									state = TokenizerState::Default;
-								},
-								'=' => {
-									i += 1;
-									let cr = src.chars().nth(i);
-									if cr.is_some() {
-										c = cr.unwrap();
-										if c == '=' {
-											tokens.push(Token::BinaryOperator(BinaryOperatorType::Equal));
-											i += 1;
-											state = TokenizerState::Default;
-											continue;
-										}
-									}
-									tokens.push(Token::Assignment);
-									state = TokenizerState::Default;
-								},
-								_ => { return Err(format!("Invalid operator {}", c)) }
+						Some(c) if number_base.is_char_valid(c) => {
+							token_value.push(c);
+							i += 1;
+						},
+						Some(c) if !Token::char_ends_token(c) => {
+							return Err(Error::Lex(format!("Invalid number literal \"{}\"", source(i + 1)), Span::new(start, i + 1)));
+						},
+						_ if token_value.is_empty() => {
+							// Only a `0x`, `0b` or `0o` prefix leaves no digits
+							return Err(Error::Lex(format!("Missing digits after \"{}\"", source(start + 2)), Span::new(start, i)));
+						},
+						_ => {
+							let span = Span::new(start, i);
+							tokens.push(SpannedToken::new(Token::parse_number(&token_value, &source(i), number_base, span)?, span));
+							token_value.clear();
+							state = TokenizerState::Default;
+						}
+					}
+				},
+				TokenizerState::Identifier => {
+					match current {
+						Some('[') if token_value == "out" => {
+							// `out[3]` is another way to write `$3`
+							let digits = chars[i + 1..].iter().take_while(|c| c.is_ascii_digit()).count();
+							if digits == 0 || chars.get(i + 1 + digits) != Some(&']') {
+								return Err(Error::Lex("Expected the number of a result in \"out[N]\"".to_string(), Span::new(start, i + 1)));
 							}
+							let number: String = chars[i + 1..i + 1 + digits].iter().collect();
+							tokens.push(SpannedToken::new(Token::Identifier(format!("${}", number)), Span::new(start, i + 2 + digits)));
+							i += 2 + digits;
+							token_value.clear();
+							state = TokenizerState::Default;
 						},
+						Some(c) if c.is_alphanumeric() || c == '_' => {
+							token_value.push(c);
+							i += 1;
+						},
+						Some(c) if !Token::char_ends_token(c) => {
+							return Err(Error::Lex(format!("Unexpected character '{}'", c), Span::new(i, i + 1)));
+						},
+						_ => {
+							let next = chars[i..].iter().find(|c| !c.is_whitespace());
+							let token = if next == Some(&'(') {
+								Token::Function(token_value.clone())
+							} else if token_value.eq_ignore_ascii_case("inf") {
+								Token::Float(f64::INFINITY)
+							} else if token_value.eq_ignore_ascii_case("nan") {
+								Token::Float(f64::NAN)
+							} else {
+								Token::Identifier(token_value.clone())
+							};
+							tokens.push(SpannedToken::new(token, Span::new(start, i)));
+							token_value.clear();
+							state = TokenizerState::Default;
+						}
 					}
 				},
-				None => break
+				TokenizerState::BinaryOperator => {
+					let Some(c) = current else { break; };
+					let (token, length) = match (c, chars.get(i + 1).copied()) {
+						('+', _) => (Token::BinaryOperator(BinaryOperatorType::Add), 1),
+						('-', _) => (Token::BinaryOperator(BinaryOperatorType::Sub), 1),
+						('*', Some('*')) => (Token::BinaryOperator(BinaryOperatorType::Exp), 2),
+						('*', _) => (Token::BinaryOperator(BinaryOperatorType::Mul), 1),
+						('/', Some('/')) => (Token::BinaryOperator(BinaryOperatorType::Fdiv), 2),
+						('/', _) => (Token::BinaryOperator(BinaryOperatorType::Div), 1),
+						('%', _) => (Token::BinaryOperator(BinaryOperatorType::Mod), 1),
+						('&', _) => (Token::BinaryOperator(BinaryOperatorType::And), 1),
+						('|', _) => (Token::BinaryOperator(BinaryOperatorType::Or), 1),
+						('^', _) => (Token::BinaryOperator(BinaryOperatorType::Xor), 1),
+						('<', Some('<')) => (Token::BinaryOperator(BinaryOperatorType::Shl), 2),
+						('<', Some('=')) => (Token::BinaryOperator(BinaryOperatorType::LessEq), 2),
+						('<', _) => (Token::BinaryOperator(BinaryOperatorType::Less), 1),
+						('>', Some('>')) => (Token::BinaryOperator(BinaryOperatorType::Shr), 2),
+						('>', Some('=')) => (Token::BinaryOperator(BinaryOperatorType::MoreEq), 2),
+						('>', _) => (Token::BinaryOperator(BinaryOperatorType::More), 1),
+						('=', Some('=')) => (Token::BinaryOperator(BinaryOperatorType::Equal), 2),
+						('=', _) => (Token::Assignment, 1),
+						_ => { return Err(Error::Lex(format!("Invalid operator {}", c), Span::new(i, i + 1))); }
+					};
+					// An operator directly followed by `=` is a compound assignment, comparisons already end in `=`
+					let (token, length) = match token {
+						Token::BinaryOperator(op) if op.is_arithmetic() && chars.get(i + length) == Some(&'=') => {
+							(Token::CompoundAssignment(op), length + 1)
+						},
+						token => (token, length)
+					};
+					tokens.push(SpannedToken::new(token, Span::new(i, i + length)));
+					i += length;
+					state = TokenizerState::Default;
+				},
 			}
-
 		}
 
-		return Ok(tokens);
+		Ok(tokens)
 	}
-}
\ No newline at end of file
+}
+
+/// A token together with the characters of the source it was read from.
+#[derive(Clone, Debug)]
+pub struct SpannedToken {
+	pub token: Token,
+	pub span: Span
+}
+impl SpannedToken {
+	pub fn new(token: Token, span: Span) -> Self {
+		Self { token, span }
+	}
+}
@@ -0,0 +1,48 @@
+use std::fmt::{Display, Formatter};
+use crate::collections::{Queue, Stack};
+use crate::token::{SpannedToken, Token};
+use crate::value::Value;
+
+/// A step of the shunting yard or of the evaluation of its output, reported to the tracer of a `Calculator`.
+pub enum TraceEvent<'a> {
+	/// The tokens of a statement, before they are sorted.
+	Tokens(&'a [SpannedToken]),
+	/// The shunting yard reads the next token.
+	Read(&'a SpannedToken),
+	/// A token is pushed onto the operator stack.
+	Push(&'a Token),
+	/// The top of the operator stack is removed, operators are output right after.
+	Pop(&'a Token),
+	/// A token is appended to the output queue.
+	Output(&'a Token),
+	/// Every token was read, the operators left on the stack are output next.
+	End,
+	/// The output queue in reverse polish notation, once every token was read.
+	Rpn(&'a Queue<SpannedToken>),
+	/// A token of the queue was evaluated, leaving these values on the stack.
+	Evaluate(&'a Token, &'a Stack<Value>)
+}
+
+/// Receives the trace events of a calculator, see `Calculator::set_tracer`.
+/// It is `Send` so a calculator can be moved to another thread together with its tracer.
+pub type Tracer = Box<dyn Fn(&TraceEvent) + Send>;
+
+/// One line per event, e.g. `eval    *  [2, 12]`.
+impl Display for TraceEvent<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			TraceEvent::Tokens(tokens) => write!(f, "tokens  {}", join(tokens.iter().map(|spanned| &spanned.token), " ")),
+			TraceEvent::Read(spanned) => write!(f, "read    {}", spanned.token),
+			TraceEvent::Push(token) => write!(f, "push    {}", token),
+			TraceEvent::Pop(token) => write!(f, "pop     {}", token),
+			TraceEvent::Output(token) => write!(f, "output  {}", token),
+			TraceEvent::End => write!(f, "end"),
+			TraceEvent::Rpn(queue) => write!(f, "rpn     {}", join(queue.iter().map(|spanned| &spanned.token), " ")),
+			TraceEvent::Evaluate(token, stack) => write!(f, "eval    {}  [{}]", token, join(stack.iter(), ", "))
+		}
+	}
+}
+
+fn join<T: Display>(items: impl Iterator<Item = T>, separator: &str) -> String {
+	items.map(|item| item.to_string()).collect::<Vec<_>>().join(separator)
+}
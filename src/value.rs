@@ -0,0 +1,394 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub};
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+use crate::error::{ArithmeticError, Error, Span};
+use crate::token::BinaryOperatorType;
+use crate::value::Value::{BigInteger, Float, Integer, Undefined};
+
+#[derive(Debug)]
+pub enum Value {
+	Undefined,
+	Integer(i64),
+	/// Integer outside the range of `i64`, results that fit are always stored as `Integer`.
+	BigInteger(BigInt),
+	Float(f64)
+}
+
+/// Both operands of a binary operation, converted to their common type.
+enum Operands {
+	Integers(i64, i64),
+	BigIntegers(BigInt, BigInt),
+	Floats(f64, f64),
+	Undefined
+}
+impl Operands {
+	fn of(left: Value, right: Value) -> Operands {
+		match (left, right) {
+			(Undefined, _) | (_, Undefined) => Operands::Undefined,
+			(Integer(l_int), Integer(r_int)) => Operands::Integers(l_int, r_int),
+			(Float(l_flt), right) => Operands::Floats(l_flt, right.as_float()),
+			(left, Float(r_flt)) => Operands::Floats(left.as_float(), r_flt),
+			(left, right) => Operands::BigIntegers(left.as_bigint().unwrap(), right.as_bigint().unwrap())
+		}
+	}
+}
+
+/// Integer results larger than this many bits fail with an overflow instead of exhausting memory.
+pub const MAX_INTEGER_BITS: u64 = 1 << 20;
+
+fn arithmetic_error(err: ArithmeticError) -> Error {
+	Error::Arithmetic(err, Span::default())
+}
+/// Checks that a big integer result stays within `MAX_INTEGER_BITS`.
+fn checked_big(big: BigInt) -> Result<Value, Error> {
+	if big.bits() > MAX_INTEGER_BITS {
+		Err(arithmetic_error(ArithmeticError::Overflow))
+	} else {
+		Ok(Value::from(big))
+	}
+}
+
+impl Value {
+	pub fn is_undefined(&self) -> bool {
+		matches!(self, Undefined)
+	}
+	pub fn is_integer(&self) -> bool {
+		matches!(self, Integer(_) | BigInteger(_))
+	}
+	/// Lower case name of the variant, e.g. "big integer".
+	pub fn type_name(&self) -> &'static str {
+		match self {
+			Undefined => "undefined",
+			Integer(_) => "integer",
+			BigInteger(_) => "big integer",
+			Float(_) => "float"
+		}
+	}
+	pub fn pow(&self, rhs: Value) -> Result<Value, Error> {
+		match Operands::of(self.clone(), rhs) {
+			Operands::Integers(l_int, r_int) if r_int >= 0 => {
+				match u32::try_from(r_int).ok().and_then(|exp| l_int.checked_pow(exp)) {
+					Some(int) => Ok(Integer(int)),
+					None => Value::big_pow(BigInt::from(l_int), BigInt::from(r_int))
+				}
+			},
+			Operands::BigIntegers(l_int, r_int) if !r_int.is_negative() => Value::big_pow(l_int, r_int),
+			Operands::Integers(..) | Operands::BigIntegers(..) => Err(arithmetic_error(ArithmeticError::NegativeExponent)),
+			Operands::Floats(l_flt, r_flt) => Ok(Float(l_flt.powf(r_flt))),
+			Operands::Undefined => Ok(Undefined)
+		}
+	}
+	fn big_pow(base: BigInt, exp: BigInt) -> Result<Value, Error> {
+		// 0, 1 and -1 stay small for any exponent
+		if base.magnitude().bits() <= 1 {
+			return match (exp.is_zero(), exp.bit(0)) {
+				(true, _) => Ok(Integer(1)),
+				(false, false) => Ok(Value::from(base.abs())),
+				(false, true) => Ok(Value::from(base))
+			};
+		}
+		match exp.to_u64() {
+			Some(exp) if (base.bits() - 1).saturating_mul(exp) <= MAX_INTEGER_BITS => checked_big(base.pow(exp as u32)),
+			_ => Err(arithmetic_error(ArithmeticError::Overflow))
+		}
+	}
+	pub fn floor(&self) -> Result<Value, Error> {
+		match self {
+			Float(flt) => Value::from_f64_integral(flt.floor()),
+			_ => Ok(self.clone())
+		}
+	}
+	pub fn ceil(&self) -> Result<Value, Error> {
+		match self {
+			Float(flt) => Value::from_f64_integral(flt.ceil()),
+			_ => Ok(self.clone())
+		}
+	}
+	pub fn round(&self) -> Result<Value, Error> {
+		match self {
+			Float(flt) => Value::from_f64_integral(flt.round()),
+			_ => Ok(self.clone())
+		}
+	}
+	pub fn as_float(&self) -> f64 {
+		match self {
+			Undefined => 0.0f64,
+			Integer(int) => *int as f64,
+			BigInteger(big) => big.to_f64().unwrap_or(f64::NAN),
+			Float(flt) => *flt
+		}
+	}
+	/// The value as an arbitrary precision integer, `None` for floats and undefined.
+	pub fn as_bigint(&self) -> Option<BigInt> {
+		match self {
+			Integer(int) => Some(BigInt::from(*int)),
+			BigInteger(big) => Some(big.clone()),
+			_ => None
+		}
+	}
+	/// Converts an already rounded float to an integer, infinity and NaN have no integer value.
+	fn from_f64_integral(flt: f64) -> Result<Value, Error> {
+		match BigInt::from_f64(flt) {
+			Some(big) => Ok(Value::from(big)),
+			None => Err(arithmetic_error(ArithmeticError::Overflow))
+		}
+	}
+}
+impl Clone for Value {
+	fn clone(&self) -> Self {
+		match self {
+			Undefined => Undefined,
+			Integer(int) => Integer(*int),
+			BigInteger(big) => BigInteger(big.clone()),
+			Float(flt) => Float(*flt)
+		}
+	}
+}
+impl From<BigInt> for Value {
+	fn from(big: BigInt) -> Self {
+		match big.to_i64() {
+			Some(int) => Integer(int),
+			None => BigInteger(big)
+		}
+	}
+}
+
+impl Neg for Value {
+	type Output = Result<Value, Error>;
+
+	fn neg(self) -> Self::Output {
+		match self {
+			Undefined => Ok(Undefined),
+			Integer(int) => {
+				match int.checked_neg() {
+					Some(int) => Ok(Integer(int)),
+					None => Ok(Value::from(-BigInt::from(int)))
+				}
+			},
+			BigInteger(big) => Ok(Value::from(-big)),
+			Float(flt) => Ok(Float(-flt))
+		}
+	}
+}
+impl Not for Value {
+	type Output = Result<Value, Error>;
+
+	fn not(self) -> Self::Output {
+		match self {
+			Undefined => Ok(Undefined),
+			Integer(int) => Ok(Integer(!int)),
+			BigInteger(big) => Ok(Value::from(!big)),
+			Float(_) => Err(Error::Type("Bitwise operations are only allowed for integer values!".to_string(), Span::default()))
+		}
+	}
+}
+impl Add for Value {
+	type Output = Result<Value, Error>;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		match Operands::of(self, rhs) {
+			Operands::Integers(l_int, r_int) => {
+				match l_int.checked_add(r_int) {
+					Some(int) => Ok(Integer(int)),
+					None => Ok(Value::from(BigInt::from(l_int) + r_int))
+				}
+			},
+			Operands::BigIntegers(l_int, r_int) => checked_big(l_int + r_int),
+			Operands::Floats(l_flt, r_flt) => Ok(Float(l_flt + r_flt)),
+			Operands::Undefined => Ok(Undefined)
+		}
+	}
+}
+impl Sub for Value {
+	type Output = Result<Value, Error>;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		match Operands::of(self, rhs) {
+			Operands::Integers(l_int, r_int) => {
+				match l_int.checked_sub(r_int) {
+					Some(int) => Ok(Integer(int)),
+					None => Ok(Value::from(BigInt::from(l_int) - r_int))
+				}
+			},
+			Operands::BigIntegers(l_int, r_int) => checked_big(l_int - r_int),
+			Operands::Floats(l_flt, r_flt) => Ok(Float(l_flt - r_flt)),
+			Operands::Undefined => Ok(Undefined)
+		}
+	}
+}
+impl Mul for Value {
+	type Output = Result<Value, Error>;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		match Operands::of(self, rhs) {
+			Operands::Integers(l_int, r_int) => {
+				match l_int.checked_mul(r_int) {
+					Some(int) => Ok(Integer(int)),
+					None => Ok(Value::from(BigInt::from(l_int) * r_int))
+				}
+			},
+			Operands::BigIntegers(l_int, r_int) => {
+				if l_int.bits() + r_int.bits() > MAX_INTEGER_BITS + 1 {
+					return Err(arithmetic_error(ArithmeticError::Overflow));
+				}
+				checked_big(l_int * r_int)
+			},
+			Operands::Floats(l_flt, r_flt) => Ok(Float(l_flt * r_flt)),
+			Operands::Undefined => Ok(Undefined)
+		}
+	}
+}
+impl Div for Value {
+	type Output = Result<Value, Error>;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		match Operands::of(self, rhs) {
+			Operands::Integers(_, 0) => Err(arithmetic_error(ArithmeticError::DivisionByZero)),
+			Operands::Integers(l_int, r_int) => {
+				match l_int.checked_div(r_int) {
+					Some(int) => Ok(Integer(int)),
+					None => Ok(Value::from(BigInt::from(l_int) / r_int))
+				}
+			},
+			Operands::BigIntegers(_, r_int) if r_int.is_zero() => Err(arithmetic_error(ArithmeticError::DivisionByZero)),
+			Operands::BigIntegers(l_int, r_int) => Ok(Value::from(l_int / r_int)),
+			Operands::Floats(l_flt, r_flt) => Ok(Float(l_flt / r_flt)),
+			Operands::Undefined => Ok(Undefined)
+		}
+	}
+}
+impl Rem for Value {
+	type Output = Result<Value, Error>;
+
+	fn rem(self, rhs: Self) -> Self::Output {
+		match Operands::of(self, rhs) {
+			Operands::Integers(_, 0) => Err(arithmetic_error(ArithmeticError::DivisionByZero)),
+			Operands::Integers(l_int, r_int) => {
+				// Only i64::MIN % -1 fails, and its remainder is zero
+				Ok(Integer(l_int.checked_rem(r_int).unwrap_or(0)))
+			},
+			Operands::BigIntegers(_, r_int) if r_int.is_zero() => Err(arithmetic_error(ArithmeticError::DivisionByZero)),
+			Operands::BigIntegers(l_int, r_int) => Ok(Value::from(l_int % r_int)),
+			Operands::Floats(l_flt, r_flt) => Ok(Float(l_flt % r_flt)),
+			Operands::Undefined => Ok(Undefined)
+		}
+	}
+}
+
+fn bitwise_float_error(op: BinaryOperatorType) -> Error {
+	Error::Type(format!("Failed to use operator '{:?}': Bitwise operators are not supported between floating-point values!", op), Span::default())
+}
+
+impl BitAnd for Value {
+	type Output = Result<Value, Error>;
+
+	fn bitand(self, rhs: Self) -> Self::Output {
+		match Operands::of(self, rhs) {
+			Operands::Integers(l_int, r_int) => Ok(Integer(l_int & r_int)),
+			Operands::BigIntegers(l_int, r_int) => Ok(Value::from(l_int & r_int)),
+			Operands::Floats(..) => Err(bitwise_float_error(BinaryOperatorType::And)),
+			Operands::Undefined => Ok(Undefined)
+		}
+	}
+}
+impl BitOr for Value {
+	type Output = Result<Value, Error>;
+
+	fn bitor(self, rhs: Self) -> Self::Output {
+		match Operands::of(self, rhs) {
+			Operands::Integers(l_int, r_int) => Ok(Integer(l_int | r_int)),
+			Operands::BigIntegers(l_int, r_int) => Ok(Value::from(l_int | r_int)),
+			Operands::Floats(..) => Err(bitwise_float_error(BinaryOperatorType::Or)),
+			Operands::Undefined => Ok(Undefined)
+		}
+	}
+}
+impl BitXor for Value {
+	type Output = Result<Value, Error>;
+
+	fn bitxor(self, rhs: Self) -> Self::Output {
+		match Operands::of(self, rhs) {
+			Operands::Integers(l_int, r_int) => Ok(Integer(l_int ^ r_int)),
+			Operands::BigIntegers(l_int, r_int) => Ok(Value::from(l_int ^ r_int)),
+			Operands::Floats(..) => Err(bitwise_float_error(BinaryOperatorType::Xor)),
+			Operands::Undefined => Ok(Undefined)
+		}
+	}
+}
+impl Shl for Value {
+	type Output = Result<Value, Error>;
+
+	fn shl(self, rhs: Self) -> Self::Output {
+		match Operands::of(self, rhs) {
+			Operands::Integers(l_int, r_int) if (0..64).contains(&r_int) && (l_int << r_int) >> r_int == l_int => {
+				Ok(Integer(l_int << r_int))
+			},
+			Operands::Integers(l_int, r_int) => big_shl(BigInt::from(l_int), BigInt::from(r_int)),
+			Operands::BigIntegers(l_int, r_int) => big_shl(l_int, r_int),
+			Operands::Floats(..) => Err(bitwise_float_error(BinaryOperatorType::Shl)),
+			Operands::Undefined => Ok(Undefined)
+		}
+	}
+}
+fn big_shl(value: BigInt, shift: BigInt) -> Result<Value, Error> {
+	match shift.to_u64() {
+		Some(_) if value.is_zero() => Ok(Integer(0)),
+		Some(shift) if value.bits().saturating_add(shift) <= MAX_INTEGER_BITS => Ok(Value::from(value << shift)),
+		_ => Err(arithmetic_error(ArithmeticError::ShiftOutOfRange))
+	}
+}
+impl Shr for Value {
+	type Output = Result<Value, Error>;
+
+	fn shr(self, rhs: Self) -> Self::Output {
+		match Operands::of(self, rhs) {
+			Operands::Integers(_, r_int) if r_int < 0 => Err(arithmetic_error(ArithmeticError::ShiftOutOfRange)),
+			Operands::Integers(l_int, r_int) if r_int >= 64 => Ok(Integer(if l_int < 0 { -1 } else { 0 })),
+			Operands::Integers(l_int, r_int) => Ok(Integer(l_int >> r_int)),
+			Operands::BigIntegers(_, r_int) if r_int.is_negative() => Err(arithmetic_error(ArithmeticError::ShiftOutOfRange)),
+			Operands::BigIntegers(l_int, r_int) => {
+				match r_int.to_usize() {
+					Some(shift) => Ok(Value::from(l_int >> shift)),
+					None => Ok(Integer(if l_int.is_negative() { -1 } else { 0 }))
+				}
+			},
+			Operands::Floats(..) => Err(bitwise_float_error(BinaryOperatorType::Shr)),
+			Operands::Undefined => Ok(Undefined)
+		}
+	}
+}
+
+impl Eq for Value {}
+
+impl PartialEq<Self> for Value {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Undefined, Undefined) => true,
+			// Mixed integers and floats compare by value, in either order
+			_ => self.partial_cmp(other) == Some(Ordering::Equal)
+		}
+	}
+}
+impl PartialOrd<Self> for Value {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		match Operands::of(self.clone(), other.clone()) {
+			Operands::Integers(l_int, r_int) => l_int.partial_cmp(&r_int),
+			Operands::BigIntegers(l_int, r_int) => l_int.partial_cmp(&r_int),
+			Operands::Floats(l_flt, r_flt) => l_flt.partial_cmp(&r_flt),
+			Operands::Undefined => None
+		}
+	}
+}
+
+impl Display for Value {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Undefined => write!(f, "undefined"),
+			Integer(int) => write!(f, "{}", int),
+			BigInteger(big) => write!(f, "{}", big),
+			Float(flt) => write!(f, "{}", flt)
+		}
+	}
+}
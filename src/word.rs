@@ -0,0 +1,74 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use num_bigint::BigInt;
+use num_traits::One;
+use crate::value::Value;
+
+/// Fixed integer width of programmer mode, integer results wrap around like machine words.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct WordSize {
+	bits: u32,
+	signed: bool
+}
+
+impl WordSize {
+	pub const WIDTHS: [u32; 5] = [8, 16, 32, 64, 128];
+
+	/// Returns `None` if `bits` is not one of `WIDTHS`.
+	pub fn new(bits: u32, signed: bool) -> Option<Self> {
+		if Self::WIDTHS.contains(&bits) {
+			Some(Self { bits, signed })
+		} else {
+			None
+		}
+	}
+	pub fn bits(&self) -> u32 {
+		self.bits
+	}
+	pub fn is_signed(&self) -> bool {
+		self.signed
+	}
+	/// Truncates an integer to the width, reading the remaining bits as two's complement if signed.
+	/// Floats and undefined values are returned unchanged.
+	pub fn wrap(&self, value: Value) -> Value {
+		let Some(pattern) = self.bit_pattern(&value) else {
+			return value;
+		};
+		if self.signed && pattern.bit(u64::from(self.bits) - 1) {
+			Value::from(pattern - self.modulus())
+		} else {
+			Value::from(pattern)
+		}
+	}
+	/// The bits of an integer at this width as an unsigned number, `None` for floats and undefined.
+	pub fn bit_pattern(&self, value: &Value) -> Option<BigInt> {
+		// Bitwise and on negative big integers works on the infinite two's complement form
+		value.as_bigint().map(|big| big & (self.modulus() - 1))
+	}
+	fn modulus(&self) -> BigInt {
+		BigInt::one() << self.bits
+	}
+}
+
+/// Prints the width the way Rust names its integer types, e.g. `u8` or `i32`.
+impl Display for WordSize {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}{}", if self.signed { "i" } else { "u" }, self.bits)
+	}
+}
+
+/// Parses the names printed by `Display`, e.g. `i16` or `u128`.
+impl FromStr for WordSize {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let signed = match s.chars().next() {
+			Some('i') => true,
+			Some('u') => false,
+			_ => { return Err(format!("Unknown word size \"{}\", expected e.g. u8 or i32", s)); }
+		};
+		s[1..].parse().ok()
+			.and_then(|bits| WordSize::new(bits, signed))
+			.ok_or_else(|| format!("Unsupported width \"{}\", expected one of {:?}", &s[1..], WordSize::WIDTHS))
+	}
+}
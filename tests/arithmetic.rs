@@ -0,0 +1,65 @@
+use std::str::FromStr;
+use num_bigint::BigInt;
+use shunting_yard_calculator::{evaluate, ArithmeticError, Error, Span, Value};
+
+fn arithmetic_error(src: &str) -> ArithmeticError {
+	match evaluate(src) {
+		Err(Error::Arithmetic(err, _)) => err,
+		result => panic!("expected an arithmetic error for \"{}\", got {:?}", src, result)
+	}
+}
+
+#[test]
+fn divides_min_by_minus_one() {
+	let expected = Value::BigInteger(BigInt::from_str("9223372036854775808").unwrap());
+	assert_eq!((Value::Integer(i64::MIN) / Value::Integer(-1)).unwrap(), expected);
+	assert_eq!((Value::Integer(i64::MIN) % Value::Integer(-1)).unwrap(), Value::Integer(0));
+	assert_eq!((Value::Integer(i64::MIN) * Value::Integer(-1)).unwrap(), expected);
+	assert_eq!((-Value::Integer(i64::MIN)).unwrap(), expected);
+	assert_eq!(evaluate("(-9223372036854775807 - 1) // -1").unwrap(), expected);
+}
+
+#[test]
+fn reports_division_by_zero() {
+	assert_eq!(arithmetic_error("1 / 0"), ArithmeticError::DivisionByZero);
+	assert_eq!(arithmetic_error("1 % 0"), ArithmeticError::DivisionByZero);
+	assert_eq!(arithmetic_error("1 // 0"), ArithmeticError::DivisionByZero);
+	assert_eq!(arithmetic_error("2 ** 100 / (1 - 1)"), ArithmeticError::DivisionByZero);
+	assert_eq!(arithmetic_error("2 ** 100 % 0"), ArithmeticError::DivisionByZero);
+	// Floats keep IEEE semantics
+	assert_eq!(evaluate("1.0 / 0").unwrap(), Value::Float(f64::INFINITY));
+}
+
+#[test]
+fn reports_negative_exponents() {
+	assert_eq!(arithmetic_error("2 ** -1"), ArithmeticError::NegativeExponent);
+	assert_eq!(arithmetic_error("2 ** -(2 ** 100)"), ArithmeticError::NegativeExponent);
+	assert_eq!(evaluate("2.0 ** -1").unwrap(), Value::Float(0.5));
+	assert_eq!(evaluate("(-1) ** (2 ** 100 + 1)").unwrap(), Value::Integer(-1));
+	assert_eq!(evaluate("0 ** 0").unwrap(), Value::Integer(1));
+}
+
+#[test]
+fn reports_shifts_out_of_range() {
+	assert_eq!(arithmetic_error("1 << -1"), ArithmeticError::ShiftOutOfRange);
+	assert_eq!(arithmetic_error("1 >> -1"), ArithmeticError::ShiftOutOfRange);
+	assert_eq!(arithmetic_error("1 << 2 ** 40"), ArithmeticError::ShiftOutOfRange);
+	assert_eq!(evaluate("0 << 2 ** 40").unwrap(), Value::Integer(0));
+	assert_eq!(evaluate("-1 >> 2 ** 40").unwrap(), Value::Integer(-1));
+}
+
+#[test]
+fn reports_overflow() {
+	assert_eq!(arithmetic_error("2 ** 2 ** 40"), ArithmeticError::Overflow);
+	assert_eq!(arithmetic_error("(2 ** 1000000) * (2 ** 1000000)"), ArithmeticError::Overflow);
+	assert_eq!(arithmetic_error("floor(1.0 / 0)"), ArithmeticError::Overflow);
+	assert_eq!(arithmetic_error("round(0.0 / 0)"), ArithmeticError::Overflow);
+	assert_eq!(arithmetic_error("(1.0 / 0) // 1"), ArithmeticError::Overflow);
+}
+
+#[test]
+fn points_at_the_operator() {
+	assert_eq!(evaluate("1 + 4 / 0").unwrap_err().span(), Span::new(6, 7));
+	assert_eq!(evaluate("floor(1.0 / 0)").unwrap_err().span(), Span::new(0, 14));
+	assert_eq!(evaluate("1 / 0").unwrap_err().to_string(), "Division by zero");
+}
@@ -0,0 +1,91 @@
+use shunting_yard_calculator::{parse, Calculator, Error, Span, Value};
+
+#[test]
+fn assigns_globals() {
+	let mut calc = Calculator::new();
+	assert_eq!(calc.calculate("width = 6").unwrap(), Value::Integer(6));
+	assert_eq!(calc.calculate("width * 2").unwrap(), Value::Integer(12));
+	assert_eq!(calc.get_var("width"), Some(&Value::Integer(6)));
+	assert_eq!(calc.calculate("width = width + 1.5").unwrap(), Value::Float(7.5));
+	assert_eq!(calc.get_var("width"), Some(&Value::Float(7.5)));
+}
+
+#[test]
+fn chains_assignments() {
+	let mut calc = Calculator::new();
+	assert_eq!(calc.calculate("a = b = 3").unwrap(), Value::Integer(3));
+	assert_eq!(calc.get_var("a"), Some(&Value::Integer(3)));
+	assert_eq!(calc.get_var("b"), Some(&Value::Integer(3)));
+	assert!(calc.calculate("x = 1 + y = 2").is_err());
+	assert_eq!(calc.calculate("x = (y = 2) + 1").unwrap(), Value::Integer(3));
+	assert_eq!(calc.get_var("y"), Some(&Value::Integer(2)));
+}
+
+#[test]
+fn assigns_inside_expressions() {
+	let mut calc = Calculator::new();
+	assert_eq!(calc.calculate("(c = 4) * 2").unwrap(), Value::Integer(8));
+	assert_eq!(calc.calculate("max(d = 2, d + 1)").unwrap(), Value::Integer(3));
+	assert_eq!(calc.get_var("c"), Some(&Value::Integer(4)));
+	// Nothing is stored if the value fails to evaluate
+	assert!(calc.calculate("e = 1 / 0").is_err());
+	assert_eq!(calc.get_var("e"), None);
+}
+
+#[test]
+fn rejects_invalid_targets() {
+	let mut calc = Calculator::new();
+	assert_eq!(calc.calculate("3 = 4"), Err(Error::Parse("Only variables can be assigned to".to_string(), Span::new(2, 3))));
+	assert_eq!(calc.calculate("1 + x = 3"), Err(Error::Parse("Only variables can be assigned to".to_string(), Span::new(6, 7))));
+	assert!(calc.calculate("-x = 3").is_err());
+	assert!(calc.calculate("x =").is_err());
+	assert!(calc.calculate("= 3").is_err());
+}
+
+#[test]
+fn builds_assignment_trees() {
+	assert_eq!(parse("a = b = 1 + 2").unwrap().to_string(), "(a = (b = (1 + 2)))");
+
+	let mut calc = Calculator::new();
+	let expr = calc.parse("total = 2 ** 4").unwrap();
+	assert_eq!(calc.evaluate_expr(&expr).unwrap(), Value::Integer(16));
+	assert_eq!(calc.get_var("total"), Some(&Value::Integer(16)));
+}
+
+#[test]
+fn updates_with_compound_assignments() {
+	let mut calc = Calculator::new();
+	calc.calculate("x = 10").unwrap();
+	let steps = [
+		("x += 5", 15), ("x -= 3", 12), ("x *= 2", 24), ("x //= 5", 4), ("x **= 3", 64), ("x %= 10", 4),
+		("x <<= 4", 64), ("x >>= 1", 32), ("x |= 3", 35), ("x &= 6", 2), ("x ^= 7", 5), ("x /= 2", 2)
+	];
+	for (src, expected) in steps {
+		assert_eq!(calc.calculate(src).unwrap(), Value::Integer(expected), "{}", src);
+		assert_eq!(calc.get_var("x"), Some(&Value::Integer(expected)), "{}", src);
+	}
+	assert_eq!(calc.calculate("x /= 4.0").unwrap(), Value::Float(0.5));
+}
+
+#[test]
+fn chains_compound_assignments() {
+	let mut calc = Calculator::new();
+	calc.calculate("total = 1").unwrap();
+	calc.calculate("step = 2").unwrap();
+	assert_eq!(calc.calculate("total += step *= 3").unwrap(), Value::Integer(7));
+	assert_eq!(calc.get_var("step"), Some(&Value::Integer(6)));
+	assert_eq!(parse("a -= b = 1 + 2").unwrap().to_string(), "(a -= (b = (1 + 2)))");
+}
+
+#[test]
+fn rejects_compound_assignment_to_undefined_variables() {
+	let mut calc = Calculator::new();
+	assert_eq!(calc.calculate("count += 1"), Err(Error::UndefinedVariable("count".to_string(), Span::new(0, 8))));
+	assert_eq!(calc.get_var("count"), None);
+	assert!(calc.calculate("1 += 1").is_err());
+	// Comparisons keep their meaning
+	calc.calculate("n = 3").unwrap();
+	assert_eq!(calc.calculate("n <= 3").unwrap(), Value::Integer(1));
+	assert_eq!(calc.calculate("n >= 4").unwrap(), Value::Integer(0));
+	assert_eq!(calc.calculate("n == 3").unwrap(), Value::Integer(1));
+}
@@ -0,0 +1,55 @@
+use shunting_yard_calculator::{parse, Calculator, Error, Expr, Span, Value};
+use shunting_yard_calculator::token::BinaryOperatorType;
+
+#[test]
+fn builds_tree_from_operator_precedence() {
+	assert_eq!(parse("1 + x * 2").unwrap(), Expr::Binary(
+		BinaryOperatorType::Add,
+		Box::new(Expr::Literal(Value::Integer(1))),
+		Box::new(Expr::Binary(
+			BinaryOperatorType::Mul,
+			Box::new(Expr::Variable("x".to_string())),
+			Box::new(Expr::Literal(Value::Integer(2)))
+		))
+	));
+}
+
+#[test]
+fn displays_fully_parenthesised() {
+	assert_eq!(parse("3 * (3 + 1)").unwrap().to_string(), "(3 * (3 + 1))");
+	assert_eq!(parse("1 + 2 * 3 < 4 & 5").unwrap().to_string(), "(((1 + (2 * 3)) < 4) & 5)");
+	assert_eq!(parse("(~x) // 2.0").unwrap().to_string(), "((~x) // 2.0)");
+}
+
+#[test]
+fn display_parses_back_to_same_tree() {
+	for src in ["1 + 2 * 3", "(1 + 2) * 3", "2 ** 3 % 5", "0xFF & 0b1010 | 1 << 2", "~3 + 1.5"] {
+		let expr = parse(src).unwrap();
+		assert_eq!(parse(&expr.to_string()).unwrap(), expr, "{}", src);
+	}
+}
+
+#[test]
+fn tree_evaluation_matches_calculate() {
+	let mut calc = Calculator::new();
+	calc.set_var(&"x".to_string(), &Value::Integer(7));
+	for src in ["1 + 2 * 3", "(1 + 2) * 3", "x // 2", "x % 4 == 3", "2 ** 10", "~x & 0xF", "1.5 * x"] {
+		let expr = calc.parse(src).unwrap();
+		assert_eq!(calc.evaluate_expr(&expr).unwrap(), calc.calculate(src).unwrap(), "{}", src);
+	}
+}
+
+#[test]
+fn reports_parse_errors() {
+	assert!(parse("1 +").is_err());
+	assert!(parse("(1 + 2").is_err());
+	assert!(parse("").is_err());
+}
+
+#[test]
+fn rejects_postfix_input() {
+	assert_eq!(parse("1 2 +"), Err(Error::Parse("Expected an operator, found \"2\"".to_string(), Span::new(2, 3))));
+	assert_eq!(parse("2 3 4 * +"), Err(Error::Parse("Expected an operator, found \"3\"".to_string(), Span::new(2, 3))));
+	assert_eq!(parse("1 (2 +)"), Err(Error::Parse("Expected an operator, found \"(\"".to_string(), Span::new(2, 3))));
+	assert_eq!(parse("+ 1"), Err(Error::Parse("Expected a value, found \"+\"".to_string(), Span::new(0, 1))));
+}
@@ -0,0 +1,62 @@
+use std::str::FromStr;
+use num_bigint::BigInt;
+use shunting_yard_calculator::{evaluate, parse, Value};
+
+fn big(src: &str) -> Value {
+	Value::BigInteger(BigInt::from_str(src).unwrap())
+}
+
+#[test]
+fn promotes_on_overflow() {
+	assert_eq!(evaluate("9223372036854775807 + 1").unwrap(), big("9223372036854775808"));
+	assert_eq!(evaluate("-9223372036854775807 - 2").unwrap(), big("-9223372036854775809"));
+	assert_eq!(evaluate("4294967296 * 4294967296").unwrap(), big("18446744073709551616"));
+	assert_eq!(evaluate("2 ** 100").unwrap(), big("1267650600228229401496703205376"));
+	assert_eq!(evaluate("1 << 70").unwrap(), big("1180591620717411303424"));
+	assert_eq!(evaluate("-(-9223372036854775807 - 1)").unwrap(), big("9223372036854775808"));
+	assert_eq!(evaluate("(-9223372036854775807 - 1) / -1").unwrap(), big("9223372036854775808"));
+	assert_eq!(evaluate("abs(-9223372036854775807 - 1)").unwrap(), big("9223372036854775808"));
+	assert_eq!(evaluate("floor(100000000000000000000.0)").unwrap(), big("100000000000000000000"));
+}
+
+#[test]
+fn demotes_results_that_fit() {
+	assert_eq!(evaluate("2 ** 64 - (2 ** 64 - 5)").unwrap(), Value::Integer(5));
+	assert_eq!(evaluate("(1 << 100) >> 99").unwrap(), Value::Integer(2));
+	assert_eq!(evaluate("(2 ** 80 + 7) % 2 ** 80").unwrap(), Value::Integer(7));
+	assert_eq!(evaluate("(2 ** 80 + 0xFF) & 0xF0").unwrap(), Value::Integer(0xF0));
+}
+
+#[test]
+fn parses_large_literals() {
+	assert_eq!(evaluate("18446744073709551616").unwrap(), big("18446744073709551616"));
+	assert_eq!(evaluate("0xFFFF_FFFF_FFFF_FFFF_FFFF").unwrap(), big("1208925819614629174706175"));
+	assert_eq!(evaluate(&format!("0b1{}", "0".repeat(64))).unwrap(), big("18446744073709551616"));
+	assert_eq!(evaluate("0o1000000000000000000000").unwrap(), big("9223372036854775808"));
+	assert_eq!(parse("18446744073709551616 + 1").unwrap().to_string(), "(18446744073709551616 + 1)");
+}
+
+#[test]
+fn compares_exactly() {
+	assert_eq!(evaluate("2 ** 64 + 1 > 2 ** 64").unwrap(), Value::Integer(1));
+	assert_eq!(evaluate("2 ** 64 == 18446744073709551616").unwrap(), Value::Integer(1));
+	assert_eq!(evaluate("max(2 ** 70, 2 ** 69, 1.5)").unwrap(), big("1180591620717411303424"));
+}
+
+#[test]
+fn compares_integers_and_floats_in_either_order() {
+	assert_eq!(evaluate("1 == 1.0").unwrap(), Value::Integer(1));
+	assert_eq!(evaluate("1.0 == 1").unwrap(), Value::Integer(1));
+	assert_eq!(evaluate("2 ** 64 != 2.0 ** 64").unwrap(), Value::Integer(0));
+	assert_eq!(evaluate("2.0 ** 64 != 2 ** 64").unwrap(), Value::Integer(0));
+	assert_eq!(Value::Integer(1) == Value::Float(1.0), Value::Float(1.0) == Value::Integer(1));
+	assert_ne!(Value::Undefined, Value::Integer(0));
+}
+
+#[test]
+fn names_value_types() {
+	assert_eq!(Value::Integer(1).type_name(), "integer");
+	assert_eq!(big("18446744073709551616").type_name(), "big integer");
+	assert_eq!(Value::Float(1.0).type_name(), "float");
+	assert_eq!(Value::Undefined.type_name(), "undefined");
+}
@@ -0,0 +1,62 @@
+use shunting_yard_calculator::{evaluate, Calculator, Error, Span, Value};
+
+#[test]
+fn evaluates_literals() {
+	assert_eq!(evaluate("123").unwrap(), Value::Integer(123));
+	assert_eq!(evaluate("0").unwrap(), Value::Integer(0));
+	assert_eq!(evaluate("1_000_000").unwrap(), Value::Integer(1_000_000));
+	assert_eq!(evaluate("0xFF").unwrap(), Value::Integer(255));
+	assert_eq!(evaluate("0b101").unwrap(), Value::Integer(5));
+	assert_eq!(evaluate("0o17").unwrap(), Value::Integer(15));
+	assert_eq!(evaluate("2.5").unwrap(), Value::Float(2.5));
+}
+
+#[test]
+fn evaluates_expressions() {
+	assert_eq!(evaluate("3 * (3 + 1)").unwrap(), Value::Integer(12));
+	assert_eq!(evaluate("1+23").unwrap(), Value::Integer(24));
+	assert_eq!(evaluate("0 + 1").unwrap(), Value::Integer(1));
+	assert_eq!(evaluate("7 // 2").unwrap(), Value::Integer(3));
+	assert_eq!(evaluate("1 << 4").unwrap(), Value::Integer(16));
+	assert_eq!(evaluate("256 >> 4").unwrap(), Value::Integer(16));
+	assert_eq!(evaluate("2 < 3").unwrap(), Value::Integer(1));
+}
+
+#[test]
+fn reads_variables() {
+	let mut calc = Calculator::new();
+	calc.set_var(&"width".to_string(), &Value::Integer(6));
+	assert_eq!(calc.calculate("width * 2").unwrap(), Value::Integer(12));
+	assert!(calc.calculate("height").is_err());
+}
+
+#[test]
+fn rejects_invalid_input() {
+	assert!(evaluate("3 $ 4").is_err());
+	assert!(evaluate("0b102").is_err());
+	assert!(evaluate("1.2.3").is_err());
+}
+
+#[test]
+fn reports_error_kinds_with_spans() {
+	assert_eq!(evaluate("3 @ 4"), Err(Error::Lex("Unexpected character '@'".to_string(), Span::new(2, 3))));
+	assert_eq!(evaluate("2 + foo"), Err(Error::UndefinedVariable("foo".to_string(), Span::new(4, 7))));
+	assert!(matches!(evaluate("~1.5"), Err(Error::Type(_, span)) if span == Span::new(0, 1)));
+	assert!(matches!(evaluate("1.5 & 1"), Err(Error::Type(_, span)) if span == Span::new(4, 5)));
+	assert!(matches!(evaluate("(1 + 2"), Err(Error::Parse(_, span)) if span == Span::new(0, 1)));
+	assert!(matches!(evaluate("1 + 2)"), Err(Error::Parse(_, span)) if span == Span::new(5, 6)));
+	assert!(matches!(evaluate("1 +"), Err(Error::Parse(_, span)) if span == Span::new(2, 3)));
+}
+
+#[test]
+fn requires_alternating_operands_and_operators() {
+	assert_eq!(evaluate("1 2 +"), Err(Error::Parse("Expected an operator, found \"2\"".to_string(), Span::new(2, 3))));
+	assert_eq!(evaluate("2 3 4 * +"), Err(Error::Parse("Expected an operator, found \"3\"".to_string(), Span::new(2, 3))));
+	assert_eq!(evaluate("1 (2 +)"), Err(Error::Parse("Expected an operator, found \"(\"".to_string(), Span::new(2, 3))));
+	assert_eq!(evaluate("(2 +)"), Err(Error::Parse("Expected a value, found \")\"".to_string(), Span::new(4, 5))));
+	assert_eq!(evaluate("2 * * 3"), Err(Error::Parse("Expected a value, found \"*\"".to_string(), Span::new(4, 5))));
+	assert_eq!(evaluate("1 ~2"), Err(Error::Parse("Expected an operator, found \"~\"".to_string(), Span::new(2, 3))));
+	assert_eq!(evaluate("2 *"), Err(Error::Parse("Expected a value after \"*\"".to_string(), Span::new(2, 3))));
+	assert_eq!(evaluate("max(1, 2) 3"), Err(Error::Parse("Expected an operator, found \"3\"".to_string(), Span::new(10, 11))));
+	assert_eq!(evaluate("-(1) * pi()"), Err(Error::UndefinedFunction("pi".to_string(), Span::new(7, 11))));
+}
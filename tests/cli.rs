@@ -0,0 +1,242 @@
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn write_script(name: &str, src: &str) -> PathBuf {
+	let path = env::temp_dir().join(format!("shunting_yard_{}_{}.calc", name, std::process::id()));
+	fs::write(&path, src).unwrap();
+	path
+}
+
+fn calculator(args: &[&str]) -> Output {
+	Command::new(env!("CARGO_BIN_EXE_ShuntingYardCalculator")).args(args).output().unwrap()
+}
+
+fn calculator_with_input(args: &[&str], input: &str) -> Output {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_ShuntingYardCalculator")).args(args)
+		.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped())
+		.spawn().unwrap();
+	child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+	child.wait_with_output().unwrap()
+}
+
+fn stdout_lines(output: &Output) -> Vec<String> {
+	String::from_utf8_lossy(&output.stdout).lines().map(str::to_string).collect()
+}
+
+#[test]
+fn runs_scripts() {
+	let path = write_script("runs", "# Derived constants\n\nradius = 2.5 # metres\n3.0 * radius ** 2\ndef sq(x) = x * x\nsq(4); sq(5)\ntotal = 1; total += 2\n");
+	let output = calculator(&["run", path.to_str().unwrap()]);
+	fs::remove_file(&path).unwrap();
+	assert!(output.status.success());
+	assert_eq!(stdout_lines(&output), ["18.75", "25"]);
+	assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn reports_script_errors_with_positions() {
+	let path = write_script("errors", "a = 1\n\nb = a +\nnever = 1\n");
+	let output = calculator(&["run", path.to_str().unwrap()]);
+	fs::remove_file(&path).unwrap();
+	assert_eq!(output.status.code(), Some(1));
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert!(stderr.starts_with(&format!("{}:3:7: Error: ", path.display())), "{}", stderr);
+	assert!(stderr.contains("b = a +\n      ^\n"), "{}", stderr);
+}
+
+#[test]
+fn fails_on_missing_files_and_bad_arguments() {
+	assert_eq!(calculator(&["run", "/nonexistent/script.calc"]).status.code(), Some(2));
+	assert_eq!(calculator(&["walk"]).status.code(), Some(2));
+}
+
+#[test]
+fn evaluates_one_expression() {
+	let output = calculator(&["-e", "2**10"]);
+	assert!(output.status.success());
+	assert_eq!(stdout_lines(&output), ["1024"]);
+
+	let output = calculator(&["-e", "hex 255; 1 / 0"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert!(output.stdout.is_empty());
+	assert_eq!(String::from_utf8_lossy(&output.stderr), "Error: Division by zero\nhex 255; 1 / 0\n           ^\n");
+
+	// Whitespace in front of the line moves the caret along
+	let output = calculator(&["-e", "   hex 1 + foo"]);
+	assert_eq!(String::from_utf8_lossy(&output.stderr), "Error: Variable \"foo\" is undefined!\n   hex 1 + foo\n           ^^^\n");
+}
+
+#[test]
+fn runs_batches_from_stdin() {
+	let output = calculator_with_input(&["--batch"], "x = 6\n\n:word u8\nx * 50\n# comment\nhex x - 7\n");
+	assert!(output.status.success());
+	assert_eq!(stdout_lines(&output), ["6", "44", "0xFF"]);
+	assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn reports_batch_errors_and_continues() {
+	// Without arguments a piped stdin is read as a batch as well
+	let output = calculator_with_input(&[], "1 +\n:walk\n2 * 3\nexit\n4\n");
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(stdout_lines(&output), ["6"]);
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert!(stderr.starts_with("<stdin>:1:3: Error: "), "{}", stderr);
+	assert!(stderr.contains("1 +\n  ^\n<stdin>:2: Error: Unknown command \":walk\"\n"), "{}", stderr);
+	assert!(!stderr.contains(">>>"), "{}", stderr);
+
+	let output = calculator_with_input(&["--batch"], "  1 +\n");
+	assert_eq!(String::from_utf8_lossy(&output.stderr), "<stdin>:1:5: Error: Expected a value after \"+\"\n  1 +\n    ^\n");
+}
+
+#[test]
+fn rejects_too_many_digits() {
+	let output = calculator_with_input(&["--batch"], ":format fix 100000000000000\nfix 18446744073709551615 1\n1.5\n");
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(stdout_lines(&output), ["1.5"]);
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert!(stderr.starts_with("<stdin>:1: Error: Expected a number of digits after \"fix\"\n<stdin>:2:"), "{}", stderr);
+}
+
+#[test]
+fn traces_on_request() {
+	let output = calculator_with_input(&["--batch"], "1 + 2\n:trace on\n-3\n:trace off\n4\n");
+	assert!(output.status.success());
+	assert_eq!(stdout_lines(&output), [
+		"3",
+		"tokens  - 3", "read    -", "push    -", "read    3", "output  3", "end", "pop     -", "output  -", "rpn     3 -",
+		"eval    3  [3]", "eval    -  [-3]", "-3",
+		"4"
+	]);
+}
+
+#[test]
+fn assigns_with_set() {
+	let output = calculator_with_input(&["--batch"], "set radius 2 * 3\nradius + 1\nset = 4; set + 1\nset x\nset y 1 +\nset z = 7\nz\nset z += 1\nset z = w\n");
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(stdout_lines(&output), ["6", "7", "5", "7", "7"]);
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert!(stderr.starts_with("<stdin>:4: Error: Usage: set NAME EXPR\n<stdin>:5:9: Error: "), "{}", stderr);
+	assert!(stderr.contains("<stdin>:8: Error: Usage: set NAME EXPR\n<stdin>:9:9: Error: Variable \"w\" is undefined!\n"), "{}", stderr);
+}
+
+#[test]
+fn explains_the_shunting_yard() {
+	let output = calculator(&["-e", "explain 2 * (3 + 4)"]);
+	assert!(output.status.success());
+	assert_eq!(stdout_lines(&output), [
+		"Token  Action                  Stack  Queue",
+		"2      output 2                       2",
+		"*      push *                  *      2",
+		"(      push (                  * (    2",
+		"3      output 3                * (    2 3",
+		"+      push +                  * ( +  2 3",
+		"4      output 4                * ( +  2 3 4",
+		")      pop +, output +, pop (  *      2 3 4 +",
+		"end    pop *, output *                2 3 4 + *",
+		"",
+		"RPN: 2 3 4 + *",
+		"Token  Result stack",
+		"2      2",
+		"3      2 3",
+		"4      2 3 4",
+		"+      2 7",
+		"*      14",
+		"",
+		"14"
+	]);
+}
+
+#[test]
+fn explains_up_to_an_error() {
+	let output = calculator(&["-e", "explain 1 + (2"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert!(stdout_lines(&output).contains(&"end    pop (     +      1 2".to_string()));
+	assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: Unclosed parenthesis\n"));
+}
+
+#[test]
+fn numbers_results() {
+	let output = calculator_with_input(&["--batch"], "x = 2 + 3\nans * 2\ndef f(a) = a\n$1 + out[2]\nhistory\n");
+	assert!(output.status.success());
+	assert_eq!(stdout_lines(&output), [
+		"5", "10", "[f(a)]", "15",
+		"    Input        Output",
+		"$1  x = 2 + 3    5",
+		"$2  ans * 2      10",
+		"$3  $1 + out[2]  15"
+	]);
+}
+
+#[test]
+fn skips_the_values_of_definitions() {
+	let output = calculator_with_input(&["--batch"], "x = 1; def f() = 2\n:echo all\nx + 1; def g(y) = y; g(3)\ndef h() = 4; def k() = 5\n");
+	assert!(output.status.success());
+	assert_eq!(stdout_lines(&output), ["[f()]", "2", "3", "[k()]"]);
+}
+
+#[test]
+fn manages_variables_with_commands() {
+	let output = calculator_with_input(&["--batch"], "x = 2\ny = 2 ** 70\ndef f(a) = a * x\nvars\ndel x f zz\ndel x f\nvars\nclear\nvars\n");
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(stdout_lines(&output), [
+		"2", "1180591620717411303424", "[f(a)]",
+		"Name  Type         Value",
+		"f     function     f(a) = (a * x)",
+		"x     integer      2",
+		"y     big integer  1180591620717411303424",
+		"Name  Type         Value",
+		"y     big integer  1180591620717411303424",
+		"No variables or functions defined"
+	]);
+	assert_eq!(String::from_utf8_lossy(&output.stderr), "<stdin>:5: Error: \"zz\" is neither a variable nor a function\n");
+}
+
+#[test]
+fn resets_the_session() {
+	let output = calculator_with_input(&["--batch"], ":word u8\nx = 300\nreset\nhistory\nvars 1\nx\n300\n");
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(stdout_lines(&output), ["44", "No results yet", "300"]);
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert!(stderr.starts_with("<stdin>:5: Error: Command \"vars\" takes no arguments\n<stdin>:6:1: Error: Variable \"x\" is undefined!\n"), "{}", stderr);
+}
+
+#[test]
+fn shows_help() {
+	let output = calculator(&["-e", "help"]);
+	assert!(output.status.success());
+	let lines = stdout_lines(&output);
+	assert!(lines.iter().any(|line| line.starts_with("Functions    sqrt")));
+	assert!(lines.iter().any(|line| line.starts_with("del NAME...  Deletes variables or functions")));
+}
+
+#[test]
+fn saves_and_loads_sessions() {
+	let path = env::temp_dir().join(format!("shunting_yard_session_{}.calc", std::process::id()));
+	let path = path.to_str().unwrap();
+	let output = calculator_with_input(&["--batch"], &format!("g = 9.81\nn = 3\ndef f(t) = g * t\nsave {}\nreset\nload {}\nvars\n", path, path));
+	let saved = fs::read_to_string(path).unwrap();
+	fs::remove_file(path).unwrap();
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert!(saved.ends_with("g = 9.81\nn = 3\ndef f(t) = (g * t)\n"), "{}", saved);
+	assert_eq!(stdout_lines(&output)[3..], [
+		"Name  Type      Value",
+		"f     function  f(t) = (g * t)",
+		"g     float     9.81",
+		"n     integer   3"
+	]);
+}
+
+#[test]
+fn reports_load_errors() {
+	let path = write_script("load", "a = 1\nb = a +\n");
+	let output = calculator_with_input(&["--batch"], &format!("load {}\na\nload /nonexistent/session.calc\n", path.display()));
+	fs::remove_file(&path).unwrap();
+	assert_eq!(stdout_lines(&output), ["1"]);
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert!(stderr.starts_with(&format!("<stdin>:1: Error: {}:2:7: ", path.display())), "{}", stderr);
+	assert!(stderr.contains("<stdin>:3: Error: Failed to read \"/nonexistent/session.calc\""), "{}", stderr);
+}
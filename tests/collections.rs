@@ -0,0 +1,17 @@
+use shunting_yard_calculator::collections::Stack;
+use shunting_yard_calculator::{evaluate, Value};
+
+#[test]
+fn peeks_at_the_top_of_the_stack() {
+	let mut stack = Stack::new();
+	stack.push(1);
+	stack.push(2);
+	assert_eq!(stack.peek(), Some(&2));
+}
+
+#[test]
+fn compares_with_the_operator_on_top() {
+	// Peeking at the bottom of the stack missed open parentheses and operators pushed later
+	assert_eq!(evaluate("3 * (3 + 1)").unwrap(), Value::Integer(12));
+	assert_eq!(evaluate("1 + 2 * 3 - 4").unwrap(), Value::Integer(3));
+}
@@ -0,0 +1,39 @@
+use shunting_yard_calculator::{Calculator, Value};
+
+fn reload(calc: &Calculator) -> Calculator {
+	let mut loaded = Calculator::new();
+	for line in calc.export().lines() {
+		loaded.calculate(line).unwrap();
+	}
+	loaded
+}
+
+#[test]
+fn exports_variables_and_functions() {
+	let mut calc = Calculator::new();
+	calc.calculate("g = 9.81; n = 3; one = 1.0; def fall(t) = g * t ** 2 / 2").unwrap();
+	assert_eq!(calc.export(), "g = 9.81\nn = 3\none = 1.0\ndef fall(t) = ((g * (t ** 2)) / 2)\n");
+}
+
+#[test]
+fn keeps_integers_and_floats_apart() {
+	let mut calc = Calculator::new();
+	calc.calculate("a = 2; b = 2.0; c = -2 ** 70; d = -0.0; e = 1e-300; f = -inf; g = nan").unwrap();
+	let loaded = reload(&calc);
+	assert_eq!(loaded.get_var("a"), Some(&Value::Integer(2)));
+	assert_eq!(loaded.get_var("b"), Some(&Value::Float(2.0)));
+	assert_eq!(loaded.get_var("c"), calc.get_var("c"));
+	assert!(matches!(loaded.get_var("d"), Some(Value::Float(flt)) if flt.to_bits() == (-0.0f64).to_bits()));
+	assert_eq!(loaded.get_var("e"), Some(&Value::Float(1e-300)));
+	assert_eq!(loaded.get_var("f"), Some(&Value::Float(f64::NEG_INFINITY)));
+	assert!(matches!(loaded.get_var("g"), Some(Value::Float(flt)) if flt.is_nan()));
+}
+
+#[test]
+fn reloads_functions() {
+	let mut calc = Calculator::new();
+	calc.calculate("scale = 0.5; def f(x, y) = max(x, y) * scale + (count = 1)").unwrap();
+	let mut loaded = reload(&calc);
+	assert_eq!(loaded.export(), calc.export());
+	assert_eq!(loaded.calculate("f(3, 4)").unwrap(), Value::Float(3.0));
+}
@@ -0,0 +1,66 @@
+use shunting_yard_calculator::{evaluate, parse, Error, Span, Value};
+
+fn lex_error(src: &str) -> (String, Span) {
+	match evaluate(src) {
+		Err(Error::Lex(msg, span)) => (msg, span),
+		result => panic!("expected a lex error for \"{}\", got {:?}", src, result)
+	}
+}
+
+#[test]
+fn parses_scientific_notation() {
+	assert_eq!(evaluate("1e-9").unwrap(), Value::Float(1e-9));
+	assert_eq!(evaluate("6.02E23").unwrap(), Value::Float(6.02e23));
+	assert_eq!(evaluate("1e+3").unwrap(), Value::Float(1000.0));
+	assert_eq!(evaluate("2.5e2 - 1e2").unwrap(), Value::Float(150.0));
+	assert_eq!(evaluate("1_000e-3").unwrap(), Value::Float(1.0));
+	// Hex digits are never read as an exponent
+	assert_eq!(evaluate("0x1e5").unwrap(), Value::Integer(0x1e5));
+}
+
+#[test]
+fn parses_leading_and_trailing_dots() {
+	assert_eq!(evaluate(".5").unwrap(), Value::Float(0.5));
+	assert_eq!(evaluate("5.").unwrap(), Value::Float(5.0));
+	assert_eq!(evaluate("-.25 * 4").unwrap(), Value::Float(-1.0));
+	assert_eq!(evaluate("max(.5, 2.)").unwrap(), Value::Float(2.0));
+	assert_eq!(evaluate("1.e3").unwrap(), Value::Float(1000.0));
+}
+
+#[test]
+fn parses_special_values() {
+	assert_eq!(evaluate("inf").unwrap(), Value::Float(f64::INFINITY));
+	assert_eq!(evaluate("-inf").unwrap(), Value::Float(f64::NEG_INFINITY));
+	assert!(evaluate("nan").unwrap().as_float().is_nan());
+	assert!(evaluate("NaN + 1").unwrap().as_float().is_nan());
+	assert_eq!(evaluate("1 / inf").unwrap(), Value::Float(0.0));
+	assert_eq!(parse("inf - 1e-9").unwrap().to_string(), "(inf - 1e-9)");
+}
+
+#[test]
+fn reports_malformed_literals() {
+	assert_eq!(lex_error("1e"), ("Missing digits in the exponent of number literal \"1e\"".to_string(), Span::new(0, 2)));
+	assert_eq!(lex_error("2 * 1e+"), ("Missing digits in the exponent of number literal \"1e+\"".to_string(), Span::new(4, 7)));
+	assert_eq!(lex_error("1e5.0"), ("Decimal point in the exponent of number literal \"1e5.\"".to_string(), Span::new(3, 4)));
+	assert_eq!(lex_error("1.2.3"), ("Second decimal point in number literal \"1.2.\"".to_string(), Span::new(3, 4)));
+	assert_eq!(lex_error("1e2e3"), ("Second exponent in number literal \"1e2e\"".to_string(), Span::new(3, 4)));
+	assert_eq!(lex_error("1e999"), ("Number literal \"1e999\" is out of range".to_string(), Span::new(0, 5)));
+	assert_eq!(lex_error("1.5x"), ("Invalid number literal \"1.5x\"".to_string(), Span::new(0, 4)));
+	assert!(evaluate(".").is_err());
+}
+
+#[test]
+fn quotes_malformed_literals_as_written() {
+	assert_eq!(lex_error("09"), ("Invalid number literal \"09\"".to_string(), Span::new(0, 2)));
+	assert_eq!(lex_error("00.5"), ("Invalid number literal \"00.\"".to_string(), Span::new(0, 3)));
+	assert_eq!(lex_error("0b12"), ("Invalid number literal \"0b12\"".to_string(), Span::new(0, 4)));
+	assert_eq!(lex_error("1_0.5.0"), ("Second decimal point in number literal \"1_0.5.\"".to_string(), Span::new(5, 6)));
+	assert_eq!(lex_error("1_0e999"), ("Number literal \"1_0e999\" is out of range".to_string(), Span::new(0, 7)));
+}
+
+#[test]
+fn reports_missing_digits_after_prefixes() {
+	assert_eq!(lex_error("0x"), ("Missing digits after \"0x\"".to_string(), Span::new(0, 2)));
+	assert_eq!(lex_error("1 + 0b_"), ("Missing digits after \"0b\"".to_string(), Span::new(4, 7)));
+	assert_eq!(lex_error("0O + 1"), ("Missing digits after \"0O\"".to_string(), Span::new(0, 2)));
+}
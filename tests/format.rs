@@ -0,0 +1,139 @@
+use num_bigint::BigInt;
+use shunting_yard_calculator::{Notation, NumberFormat, Value, WordSize};
+use shunting_yard_calculator::format::{float_in_base, hex_float, MAX_DIGITS};
+use shunting_yard_calculator::token::NumberBaseType;
+
+fn format(notation: Notation, value: Value) -> String {
+	NumberFormat::new(notation, None).format(&value)
+}
+
+#[test]
+fn formats_scientific() {
+	assert_eq!(format(Notation::Scientific, Value::Float(123400.0)), "1.234e5");
+	assert_eq!(format(Notation::Scientific, Value::Float(-0.00056)), "-5.6e-4");
+	assert_eq!(format(Notation::Scientific, Value::Integer(1000)), "1e3");
+	assert_eq!(format(Notation::Scientific, Value::Integer(0)), "0e0");
+	assert_eq!(format(Notation::Scientific, Value::Float(f64::INFINITY)), "inf");
+}
+
+#[test]
+fn formats_engineering() {
+	assert_eq!(format(Notation::Engineering, Value::Float(123456789.0)), "123.456789M");
+	assert_eq!(format(Notation::Engineering, Value::Float(4.7e-6)), "4.7µ");
+	assert_eq!(format(Notation::Engineering, Value::Float(0.015)), "15m");
+	assert_eq!(format(Notation::Engineering, Value::Integer(-2200)), "-2.2k");
+	assert_eq!(format(Notation::Engineering, Value::Integer(42)), "42");
+	assert_eq!(format(Notation::Engineering, Value::Float(1e40)), "10e39");
+}
+
+#[test]
+fn formats_fixed_places() {
+	assert_eq!(format(Notation::Fixed(2), Value::Float(1.0 / 3.0)), "0.33");
+	assert_eq!(format(Notation::Fixed(3), Value::Float(-2.0005)), "-2.001");
+	assert_eq!(format(Notation::Fixed(0), Value::Float(2.5)), "2");
+	assert_eq!(format(Notation::Fixed(2), Value::Integer(5)), "5.00");
+}
+
+#[test]
+fn formats_significant_digits() {
+	assert_eq!(format(Notation::Significant(3), Value::Float(2.0 / 3.0)), "0.667");
+	assert_eq!(format(Notation::Significant(3), Value::Float(0.00012345)), "0.000123");
+	assert_eq!(format(Notation::Significant(3), Value::Float(2.0)), "2.00");
+	assert_eq!(format(Notation::Significant(2), Value::Integer(123456)), "120000");
+	assert_eq!(format(Notation::Significant(3), Value::Float(999.9)), "1000");
+	assert_eq!(format(Notation::Significant(1), Value::Integer(-95)), "-100");
+}
+
+#[test]
+fn groups_digits() {
+	let format = NumberFormat::new(Notation::Default, Some(','));
+	assert_eq!(format.format(&Value::Integer(1234567)), "1,234,567");
+	assert_eq!(format.format(&Value::Integer(-123456)), "-123,456");
+	assert_eq!(format.format(&Value::Float(1234.5678)), "1,234.5678");
+	assert_eq!(format.format(&Value::Integer(999)), "999");
+
+	let format = NumberFormat::new(Notation::Fixed(2), Some('_'));
+	assert_eq!(format.format(&Value::Float(1e6)), "1_000_000.00");
+}
+
+#[test]
+fn parses_notations() {
+	assert_eq!("sci".parse(), Ok(Notation::Scientific));
+	assert_eq!("fix 4".parse(), Ok(Notation::Fixed(4)));
+	assert_eq!("sig 3".parse(), Ok(Notation::Significant(3)));
+	assert!("sig 0".parse::<Notation>().is_err());
+	assert!("fix".parse::<Notation>().is_err());
+	assert!("sci 2".parse::<Notation>().is_err());
+	assert_eq!(Notation::Fixed(4).to_string(), "fix 4");
+}
+
+#[test]
+fn limits_the_number_of_digits() {
+	assert_eq!(format!("fix {}", MAX_DIGITS).parse(), Ok(Notation::Fixed(MAX_DIGITS)));
+	assert_eq!(format(Notation::Fixed(MAX_DIGITS), Value::Float(1.5)).len(), MAX_DIGITS + 2);
+	assert_eq!(format(Notation::Significant(MAX_DIGITS), Value::Float(1.5)).len(), MAX_DIGITS + 1);
+	assert_eq!("fix 100000000000000".parse::<Notation>(), Err("Expected a number of digits after \"fix\"".to_string()));
+	assert_eq!("sig 18446744073709551615".parse::<Notation>(), Err("Expected a number of digits after \"sig\"".to_string()));
+}
+
+#[test]
+fn formats_hex_floats() {
+	assert_eq!(hex_float(3.0), "0x1.8p+1");
+	assert_eq!(hex_float(1.0), "0x1p+0");
+	assert_eq!(hex_float(0.1), "0x1.999999999999ap-4");
+	assert_eq!(hex_float(-0.0), "-0x0p+0");
+	assert_eq!(hex_float(f64::MIN_POSITIVE / 2.0), "0x0.8p-1022");
+}
+
+#[test]
+fn expands_fractions_in_base() {
+	assert_eq!(float_in_base(3.25, 2, None), "0b11.01");
+	assert_eq!(float_in_base(-0.5, 8, None), "-0o0.4");
+	assert_eq!(float_in_base(0.1, 2, Some(8)), "0b0.00011001");
+	assert_eq!(float_in_base(1e20, 2, Some(4)), format!("0b{:b}", 100_000_000_000_000_000_000u128));
+	// Every binary fraction terminates
+	assert_eq!(float_in_base(0.1, 2, None).len(), "0b0.".len() + 55);
+}
+
+#[test]
+fn formats_floats_in_other_bases() {
+	let mut format = NumberFormat { base: NumberBaseType::Hex, ..NumberFormat::default() };
+	assert_eq!(format.format(&Value::Float(3.0)), "0x1.8p+1");
+	assert_eq!(format.format(&Value::Float(f64::INFINITY)), "inf");
+	assert_eq!(format.format(&Value::Integer(255)), "0xFF");
+	format.base = NumberBaseType::Octal;
+	format.precision = Some(3);
+	assert_eq!(format.format(&Value::Float(0.1)), "0o0.063");
+}
+
+#[test]
+fn shows_raw_bits() {
+	let mut format = NumberFormat { raw_bits: true, base: NumberBaseType::Hex, ..NumberFormat::default() };
+	assert_eq!(format.format(&Value::Float(1.5)), "0x3FF8000000000000");
+	assert_eq!(format.format(&Value::Float(-0.0)), "0x8000000000000000");
+	// Integers have no IEEE representation and are shown as usual
+	assert_eq!(format.format(&Value::Integer(7)), "0x7");
+	format.base = NumberBaseType::Binary;
+	assert_eq!(format.format(&Value::Float(1.5)), format!("0b0_01111111111_1{}", "0".repeat(51)));
+	format.base = NumberBaseType::Decimal;
+	assert_eq!(format.format(&Value::Float(1.5)), "4609434218613702656");
+}
+
+#[test]
+fn formats_negative_integers_in_other_bases() {
+	let mut format = NumberFormat { base: NumberBaseType::Hex, ..NumberFormat::default() };
+	assert_eq!(format.format(&Value::Integer(-1)), "0xFFFFFFFFFFFFFFFF");
+	assert_eq!(format.format(&Value::BigInteger(BigInt::from(-1) << 70)), "-0x400000000000000000");
+	format.base = NumberBaseType::Binary;
+	assert_eq!(format.format(&Value::Integer(-5)), format!("0b{}011", "1".repeat(61)));
+	format.base = NumberBaseType::Octal;
+	assert_eq!(format.format(&Value::Integer(-8)), "0o1777777777777777777770");
+}
+
+#[test]
+fn pads_integers_to_the_word_size() {
+	let mut format = NumberFormat { base: NumberBaseType::Hex, word_size: WordSize::new(16, true), ..NumberFormat::default() };
+	assert_eq!(format.format(&Value::Integer(-1)), "0xFFFF");
+	format.base = NumberBaseType::Binary;
+	assert_eq!(format.format(&Value::Integer(5)), "0b0000000000000101");
+}
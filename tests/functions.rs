@@ -0,0 +1,54 @@
+use shunting_yard_calculator::{evaluate, parse, Calculator, Error, Span, Value};
+
+#[test]
+fn calls_builtins() {
+	assert_eq!(evaluate("sqrt(16)").unwrap(), Value::Float(4.0));
+	assert_eq!(evaluate("abs(-5)").unwrap(), Value::Integer(5));
+	assert_eq!(evaluate("abs(-2.5)").unwrap(), Value::Float(2.5));
+	assert_eq!(evaluate("min(3, 1.5, 2)").unwrap(), Value::Float(1.5));
+	assert_eq!(evaluate("max(3, 1.5, 2)").unwrap(), Value::Integer(3));
+	assert_eq!(evaluate("floor(2.7) + ceil(2.2) + round(2.5)").unwrap(), Value::Integer(8));
+	assert_eq!(evaluate("log(1000)").unwrap(), Value::Float(3.0));
+	assert_eq!(evaluate("log(8, 2)").unwrap(), Value::Float(3.0));
+	assert_eq!(evaluate("log2(1024)").unwrap(), Value::Float(10.0));
+	assert_eq!(evaluate("ln(exp(2))").unwrap(), Value::Float(2.0));
+	assert_eq!(evaluate("sin(0) + cos(0) + tanh(0)").unwrap(), Value::Float(1.0));
+	assert_eq!(evaluate("atan2(1, 1) * 4").unwrap(), Value::Float(std::f64::consts::PI));
+}
+
+#[test]
+fn arguments_are_full_expressions() {
+	assert_eq!(evaluate("max(1 + 2 * 3, (4 - 1) ** 2) - 1").unwrap(), Value::Integer(8));
+	assert_eq!(evaluate("-abs(-3) ** 2").unwrap(), Value::Integer(-9));
+	assert_eq!(evaluate("min(max(1, 2), max(3, 4))").unwrap(), Value::Integer(2));
+	assert_eq!(evaluate("sqrt (4)").unwrap(), Value::Float(2.0));
+
+	let mut calc = Calculator::new();
+	calc.set_var(&"x".to_string(), &Value::Integer(-4));
+	assert_eq!(calc.calculate("abs(x) + x").unwrap(), Value::Integer(0));
+	let expr = calc.parse("max(x, abs(x) // 3)").unwrap();
+	assert_eq!(expr.to_string(), "max(x, (abs(x) // 3))");
+	assert_eq!(calc.evaluate_expr(&expr).unwrap(), Value::Integer(1));
+}
+
+#[test]
+fn reports_call_errors() {
+	assert!(matches!(evaluate("foo(1)"), Err(Error::UndefinedFunction(name, _)) if name == "foo"));
+	assert!(matches!(evaluate("sqrt(1, 2)"), Err(Error::Type(..))));
+	assert!(matches!(evaluate("max()"), Err(Error::Type(..))));
+	assert!(matches!(evaluate("1, 2"), Err(Error::Parse(..))));
+	assert!(matches!(evaluate("(1, 2)"), Err(Error::Parse(..))));
+	assert!(parse("max(1 2)").is_err());
+}
+
+#[test]
+fn rejects_empty_arguments() {
+	assert_eq!(evaluate("max(1,)"), Err(Error::Parse("Empty argument".to_string(), Span::new(6, 7))));
+	assert_eq!(evaluate("max(,1)"), Err(Error::Parse("Empty argument".to_string(), Span::new(4, 5))));
+	assert_eq!(evaluate("max(,)"), Err(Error::Parse("Empty argument".to_string(), Span::new(4, 5))));
+	assert_eq!(evaluate("max(1, , 2)"), Err(Error::Parse("Empty argument".to_string(), Span::new(7, 8))));
+	assert_eq!(evaluate("3 max(1,)"), Err(Error::Parse("Expected an operator, found \"max\"".to_string(), Span::new(2, 5))));
+	assert_eq!(evaluate("()"), Err(Error::Parse("Expected a value, found \")\"".to_string(), Span::new(1, 2))));
+	assert_eq!(evaluate("2 * ()"), Err(Error::Parse("Expected a value, found \")\"".to_string(), Span::new(5, 6))));
+	assert_eq!(evaluate("max((), 1)"), Err(Error::Parse("Expected a value, found \")\"".to_string(), Span::new(5, 6))));
+}
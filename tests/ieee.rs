@@ -0,0 +1,55 @@
+use shunting_yard_calculator::{evaluate, Error, Value};
+use shunting_yard_calculator::ieee::FloatInfo;
+
+#[test]
+fn reinterprets_bits() {
+	assert_eq!(evaluate("f32bits(1.0)").unwrap(), Value::Integer(0x3F80_0000));
+	assert_eq!(evaluate("f64bits(1.5)").unwrap(), Value::Integer(0x3FF8_0000_0000_0000));
+	assert_eq!(evaluate("f64bits(-2.0) == 0xC000000000000000").unwrap(), Value::Integer(1));
+	assert_eq!(evaluate("f64bits(2)").unwrap(), Value::Integer(0x4000_0000_0000_0000));
+	assert_eq!(evaluate("fromf32bits(0x3F800000)").unwrap(), Value::Float(1.0));
+	assert_eq!(evaluate("fromf32bits(f32bits(0.1))").unwrap(), Value::Float(f64::from(0.1f32)));
+	assert_eq!(evaluate("fromf64bits(f64bits(0.1))").unwrap(), Value::Float(0.1));
+	// Negative patterns are read as two's complement, as produced by signed word sizes
+	assert_eq!(evaluate("fromf64bits(-0x4000000000000000)").unwrap(), Value::Float(-2.0));
+}
+
+#[test]
+fn rejects_invalid_bit_patterns() {
+	assert!(matches!(evaluate("fromf32bits(2 ** 32)"), Err(Error::Type(..))));
+	assert!(matches!(evaluate("fromf64bits(1.5)"), Err(Error::Type(..))));
+	assert!(matches!(evaluate("fromf32bits(-(2 ** 31) - 1)"), Err(Error::Type(..))));
+}
+
+#[test]
+fn breaks_floats_into_fields() {
+	let info = FloatInfo::of_f64(-1.5);
+	assert!(info.negative);
+	assert_eq!(info.biased_exponent, 1023);
+	assert_eq!(info.exponent(), 0);
+	assert_eq!(info.mantissa, 0x8_0000_0000_0000);
+	assert_eq!(info.significand(), 1.5);
+	assert_eq!(info.class(), "normal");
+	assert_eq!(info.bits(), (-1.5f64).to_bits());
+
+	let info = FloatInfo::of_f32(0.1);
+	assert_eq!(info.bits(), u64::from(0.1f32.to_bits()));
+	assert_eq!(info.exponent(), -4);
+
+	assert_eq!(FloatInfo::of_f64(5e-324).class(), "subnormal");
+	assert_eq!(FloatInfo::of_f64(5e-324).exponent(), -1022);
+	assert_eq!(FloatInfo::of_f64(f64::NEG_INFINITY).class(), "infinite");
+	assert_eq!(FloatInfo::of_f32(f32::NAN).class(), "nan");
+	assert_eq!(FloatInfo::of_f64(0.0).class(), "zero");
+}
+
+#[test]
+fn displays_fields() {
+	assert_eq!(FloatInfo::of_f64(3.0).to_string(), [
+		"bits      0x4008000000000000",
+		"sign      0  +",
+		"exponent  0b10000000000  1024  2^1",
+		"mantissa  0x8000000000000  1.5",
+		"class     normal"
+	].join("\n"));
+}
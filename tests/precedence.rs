@@ -0,0 +1,63 @@
+use shunting_yard_calculator::{evaluate, parse, Value};
+
+fn check(table: &[(&str, Value)]) {
+	for (src, expected) in table {
+		assert_eq!(&evaluate(src).unwrap(), expected, "{}", src);
+	}
+}
+
+#[test]
+fn left_associative_chains() {
+	check(&[
+		("2 + 3 + 4", Value::Integer(9)),
+		("2 - 3 - 4", Value::Integer(-5)),
+		("1 - 2 + 3", Value::Integer(2)),
+		("2 * 3 * 4", Value::Integer(24)),
+		("100 / 10 / 5", Value::Integer(2)),
+		("8 / 2 * 4", Value::Integer(16)),
+		("2 * 3 / 4", Value::Integer(1)),
+		("100 // 7 // 2", Value::Integer(7)),
+		("7 // 2 * 2", Value::Integer(6)),
+		("100 % 7 % 3", Value::Integer(2)),
+		("1 << 2 << 3", Value::Integer(32)),
+		("256 >> 2 >> 1", Value::Integer(32)),
+		("12 & 10 & 6", Value::Integer(0)),
+		("1 | 2 | 4", Value::Integer(7)),
+		("7 ^ 5 ^ 1", Value::Integer(3)),
+		("1 < 2 < 3", Value::Integer(1)),
+		("3 > 2 > 1", Value::Integer(0)),
+		("2 == 2 == 1", Value::Integer(1)),
+	]);
+}
+
+#[test]
+fn right_associative_exponent() {
+	check(&[
+		("2 ** 3 ** 2", Value::Integer(512)),
+		("2 ** 2 ** 3", Value::Integer(256)),
+		("(2 ** 3) ** 2", Value::Integer(64)),
+		("2.0 ** 2 ** -1.0", Value::Float(2.0f64.sqrt())),
+	]);
+}
+
+#[test]
+fn unary_operators() {
+	check(&[
+		("-2 + 3", Value::Integer(1)),
+		("-2 ** 2", Value::Integer(-4)),
+		("2 ** -1.0", Value::Float(0.5)),
+		("(-3) * 2", Value::Integer(-6)),
+		("-(2 + 3)", Value::Integer(-5)),
+		("2 - -3", Value::Integer(5)),
+		("~0 & 0xF", Value::Integer(15)),
+		("!0 + 1", Value::Integer(2)),
+	]);
+}
+
+#[test]
+fn parenthesises_by_associativity() {
+	assert_eq!(parse("2 - 3 - 4").unwrap().to_string(), "((2 - 3) - 4)");
+	assert_eq!(parse("2 ** 3 ** 2").unwrap().to_string(), "(2 ** (3 ** 2))");
+	assert_eq!(parse("-2 ** 2").unwrap().to_string(), "(-(2 ** 2))");
+	assert_eq!(parse("~x // 2.0").unwrap().to_string(), "((~x) // 2.0)");
+}
@@ -0,0 +1,49 @@
+use shunting_yard_calculator::{Calculator, Error, Span, Token, Value};
+
+#[test]
+fn reads_numbered_results() {
+	let mut calc = Calculator::new();
+	assert_eq!(calc.push_result(Value::Integer(5)), 1);
+	assert_eq!(calc.push_result(Value::Float(2.5)), 2);
+	assert_eq!(calc.calculate("$1 * 2").unwrap(), Value::Integer(10));
+	assert_eq!(calc.calculate("out[1] + out[2]").unwrap(), Value::Float(7.5));
+	assert_eq!(calc.calculate("ans").unwrap(), Value::Float(2.5));
+	assert_eq!(calc.calculate("-_").unwrap(), Value::Float(-2.5));
+	assert_eq!(calc.results(), [Value::Integer(5), Value::Float(2.5)]);
+}
+
+#[test]
+fn calculate_does_not_number_results() {
+	let mut calc = Calculator::new();
+	calc.calculate("1 + 1").unwrap();
+	assert!(calc.results().is_empty());
+	assert_eq!(calc.calculate("ans"), Err(Error::UndefinedVariable("ans".to_string(), Span::new(0, 3))));
+	assert_eq!(calc.calculate("2 * $1"), Err(Error::UndefinedVariable("$1".to_string(), Span::new(4, 6))));
+}
+
+#[test]
+fn variables_hide_results() {
+	let mut calc = Calculator::new();
+	calc.push_result(Value::Integer(1));
+	calc.calculate("ans = 7").unwrap();
+	assert_eq!(calc.calculate("ans").unwrap(), Value::Integer(7));
+	assert_eq!(calc.calculate("_").unwrap(), Value::Integer(1));
+}
+
+#[test]
+fn results_are_read_only() {
+	let mut calc = Calculator::new();
+	calc.push_result(Value::Integer(1));
+	assert_eq!(calc.calculate("$1 = 2"), Err(Error::Parse("Only variables can be assigned to".to_string(), Span::new(3, 4))));
+}
+
+#[test]
+fn tokenizes_result_references() {
+	let tokens = Token::tokenize("$12 + out[3]").unwrap();
+	assert_eq!(tokens[0].token.to_string(), "$12");
+	assert_eq!(tokens[0].span, Span::new(0, 3));
+	assert_eq!(tokens[2].token.to_string(), "$3");
+	assert_eq!(tokens[2].span, Span::new(6, 12));
+	assert!(matches!(Token::tokenize("$ + 1"), Err(Error::Lex(_, span)) if span == Span::new(0, 1)));
+	assert!(matches!(Token::tokenize("out[x]"), Err(Error::Lex(_, span)) if span == Span::new(0, 4)));
+}
@@ -0,0 +1,29 @@
+use shunting_yard_calculator::{evaluate, Calculator, Error, Span, Value};
+
+#[test]
+fn evaluates_statements_in_order() {
+	assert_eq!(evaluate("a = 3; b = a * 2; a + b").unwrap(), Value::Integer(9));
+	assert_eq!(evaluate("1;2;3").unwrap(), Value::Integer(3));
+	// Empty statements are skipped, so a trailing semicolon is allowed
+	assert_eq!(evaluate("x = 4;").unwrap(), Value::Integer(4));
+	assert_eq!(evaluate("; 5 ;;").unwrap(), Value::Integer(5));
+}
+
+#[test]
+fn returns_every_value() {
+	let mut calc = Calculator::new();
+	assert_eq!(calc.calculate_all("a = 3; b = a * 2; a + b").unwrap(), vec![Value::Integer(3), Value::Integer(6), Value::Integer(9)]);
+	assert_eq!(calc.calculate_all("def twice(x) = x * 2; twice(b)").unwrap(), vec![Value::Undefined, Value::Integer(12)]);
+}
+
+#[test]
+fn stops_at_the_first_error() {
+	let mut calc = Calculator::new();
+	assert_eq!(calc.calculate("a = 1; b = c; d = 2"), Err(Error::UndefinedVariable("c".to_string(), Span::new(11, 12))));
+	// Statements before the error keep their effect
+	assert_eq!(calc.get_var("a"), Some(&Value::Integer(1)));
+	assert_eq!(calc.get_var("d"), None);
+	assert!(matches!(calc.calculate("1; 2 +; 3"), Err(Error::Parse(_, span)) if span == Span::new(5, 6)));
+	assert!(calc.calculate(";").is_err());
+	assert!(calc.calculate("(1; 2)").is_err());
+}
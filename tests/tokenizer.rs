@@ -0,0 +1,21 @@
+use shunting_yard_calculator::{evaluate, Calculator, Value};
+
+#[test]
+fn reads_tokens_at_the_end_of_the_input() {
+	// The last character of a number or name used to be dropped or never read
+	assert_eq!(evaluate("123").unwrap(), Value::Integer(123));
+	assert_eq!(evaluate("1+23").unwrap(), Value::Integer(24));
+	assert_eq!(evaluate("2.5").unwrap(), Value::Float(2.5));
+	assert_eq!(evaluate("0xFF").unwrap(), Value::Integer(255));
+	let mut calc = Calculator::new();
+	calc.set_var(&"width".to_string(), &Value::Integer(6));
+	assert_eq!(calc.calculate("2 * width").unwrap(), Value::Integer(12));
+	assert!(calc.calculate("height").is_err());
+}
+
+#[test]
+fn keeps_a_leading_zero() {
+	// A plain `0` in front of other tokens used to leave no digits to parse
+	assert_eq!(evaluate("0 + 1").unwrap(), Value::Integer(1));
+	assert_eq!(evaluate("1 + 0").unwrap(), Value::Integer(1));
+}
@@ -0,0 +1,72 @@
+use std::sync::{Arc, Mutex};
+use shunting_yard_calculator::{Calculator, Value};
+
+fn traced(src: &str) -> (Value, Vec<String>) {
+	let lines = Arc::new(Mutex::new(Vec::new()));
+	let mut calc = Calculator::new();
+	let sink = lines.clone();
+	calc.set_tracer(Some(Box::new(move |event| sink.lock().unwrap().push(event.to_string()))));
+	let value = calc.calculate(src).unwrap();
+	let lines = lines.lock().unwrap().clone();
+	(value, lines)
+}
+
+#[test]
+fn traces_the_shunting_yard() {
+	let (value, lines) = traced("2 * (3 + 4)");
+	assert_eq!(value, Value::Integer(14));
+	assert_eq!(lines[..21], [
+		"tokens  2 * ( 3 + 4 )",
+		"read    2", "output  2",
+		"read    *", "push    *",
+		"read    (", "push    (",
+		"read    3", "output  3",
+		"read    +", "push    +",
+		"read    4", "output  4",
+		"read    )", "pop     +", "output  +", "pop     (",
+		"end", "pop     *", "output  *",
+		"rpn     2 3 4 + *"
+	]);
+}
+
+#[test]
+fn traces_the_evaluation_stack() {
+	let (_, lines) = traced("max(1, 2) - 3");
+	let evaluation: Vec<_> = lines.iter().skip_while(|line| !line.starts_with("rpn")).collect();
+	assert_eq!(evaluation, [
+		"rpn     1 2 max/2 3 -",
+		"eval    1  [1]",
+		"eval    2  [1, 2]",
+		"eval    max/2  [2]",
+		"eval    3  [2, 3]",
+		"eval    -  [-1]"
+	]);
+}
+
+#[test]
+fn traces_assignments_as_stores() {
+	let (_, lines) = traced("x = 2.5");
+	assert!(lines.contains(&"push    x=".to_string()), "{:?}", lines);
+	assert!(lines.contains(&"rpn     2.5 x=".to_string()), "{:?}", lines);
+}
+
+#[test]
+fn is_silent_without_a_tracer() {
+	let mut calc = Calculator::new();
+	assert!(!calc.is_tracing());
+	assert!(calc.set_tracer(Some(Box::new(|_| {}))).is_none());
+	assert!(calc.is_tracing());
+	assert!(calc.set_tracer(None).is_some());
+	assert!(!calc.is_tracing());
+}
+
+#[test]
+fn moves_to_other_threads() {
+	let mut calc = Calculator::new();
+	calc.set_tracer(Some(Box::new(|_| {})));
+	let calc = std::thread::spawn(move || {
+		calc.calculate("x = 2 * 3").unwrap();
+		calc
+	}).join().unwrap();
+	assert_eq!(calc.get_var("x"), Some(&Value::Integer(6)));
+}
@@ -0,0 +1,102 @@
+use shunting_yard_calculator::{Calculator, Error, Value};
+
+#[test]
+fn defines_and_calls_functions() {
+	let mut calc = Calculator::new();
+	assert_eq!(calc.calculate("def f(x, y) = x ** 2 + y").unwrap(), Value::Undefined);
+	assert_eq!(calc.calculate("f(3, 1)").unwrap(), Value::Integer(10));
+	assert_eq!(calc.calculate("f(f(1, 1), 0) * 2").unwrap(), Value::Integer(8));
+
+	calc.calculate("def half(x) = x / 2.0").unwrap();
+	calc.calculate("def quarter(x) = half(half(x))").unwrap();
+	assert_eq!(calc.calculate("quarter(10)").unwrap(), Value::Float(2.5));
+
+	calc.calculate("def seven() = 7").unwrap();
+	assert_eq!(calc.calculate("seven() + 1").unwrap(), Value::Integer(8));
+}
+
+#[test]
+fn arguments_shadow_globals() {
+	let mut calc = Calculator::new();
+	calc.set_var(&"x".to_string(), &Value::Integer(100));
+	calc.set_var(&"offset".to_string(), &Value::Integer(1));
+	calc.calculate("def inc(x) = x + offset").unwrap();
+	assert_eq!(calc.calculate("inc(5)").unwrap(), Value::Integer(6));
+	assert_eq!(calc.calculate("x").unwrap(), Value::Integer(100));
+
+	// A function only sees its own arguments, not the ones of its caller
+	calc.calculate("def inner() = y").unwrap();
+	calc.calculate("def outer(y) = inner()").unwrap();
+	assert!(matches!(calc.calculate("outer(1)"), Err(Error::UndefinedVariable(name, _)) if name == "y"));
+}
+
+#[test]
+fn checks_arity() {
+	let mut calc = Calculator::new();
+	calc.calculate("def f(a, b) = a - b").unwrap();
+	assert!(matches!(calc.calculate("f(1)"), Err(Error::Type(..))));
+	assert!(matches!(calc.calculate("f(1, 2, 3)"), Err(Error::Type(..))));
+}
+
+#[test]
+fn limits_recursion_depth() {
+	let mut calc = Calculator::new();
+	calc.calculate("def f(x) = f(x)").unwrap();
+	let err = calc.calculate("1 + f(1)").unwrap_err();
+	assert!(matches!(err, Error::Recursion(ref name, _) if name == "f"));
+	assert_eq!(err.span().start, 4);
+	// The calculator is still usable afterwards
+	assert_eq!(calc.calculate("1 + 1").unwrap(), Value::Integer(2));
+}
+
+#[test]
+fn limits_the_depth_of_nested_bodies() {
+	// Every level of the body takes room on the stack, not only the calls
+	for levels in [20, 100, 1000] {
+		let mut calc = Calculator::new();
+		let body = format!("{}f(x){}", "1 + (".repeat(levels), ")".repeat(levels));
+		calc.calculate(&format!("def f(x) = {}", body)).unwrap();
+		let err = calc.calculate("f(1)").unwrap_err();
+		assert!(matches!(err, Error::Recursion(ref name, _) if name == "f"), "{:?}", err);
+		assert_eq!(err.span().start, 0);
+	}
+	// Deep bodies that end are still evaluated
+	let mut calc = Calculator::new();
+	calc.calculate(&format!("def g(x) = {}x{}", "1 + (".repeat(200), ")".repeat(200))).unwrap();
+	assert_eq!(calc.calculate("g(1)").unwrap(), Value::Integer(201));
+}
+
+#[test]
+fn rejects_invalid_definitions() {
+	let mut calc = Calculator::new();
+	assert!(matches!(calc.calculate("def f = 1"), Err(Error::Parse(..))));
+	assert!(matches!(calc.calculate("def f(x, x) = x"), Err(Error::Parse(..))));
+	assert!(matches!(calc.calculate("def f(x) x"), Err(Error::Parse(..))));
+	assert!(matches!(calc.calculate("def f(1) = 1"), Err(Error::Parse(..))));
+	assert!(matches!(calc.calculate("def sqrt(x) = x"), Err(Error::Parse(..))));
+	assert!(matches!(calc.calculate("def f(x) ="), Err(Error::Parse(..))));
+	assert!(calc.get_function("f").is_none());
+}
+
+#[test]
+fn lists_globals_and_functions() {
+	let mut calc = Calculator::new();
+	calc.calculate("width = 3; def area(h) = width * h; area(2)").unwrap();
+	assert_eq!(calc.globals().keys().collect::<Vec<_>>(), ["width"]);
+	assert_eq!(calc.globals()["width"], Value::Integer(3));
+	assert_eq!(calc.functions()["area"].params, ["h"]);
+}
+
+#[test]
+fn removes_globals_and_functions() {
+	let mut calc = Calculator::new();
+	calc.calculate("width = 3; height = 4; def area(h) = width * h").unwrap();
+	assert_eq!(calc.remove_var("width"), Some(Value::Integer(3)));
+	assert_eq!(calc.remove_var("width"), None);
+	assert!(calc.remove_function("area").is_some());
+	assert!(calc.calculate("area(1)").is_err());
+	calc.calculate("def area(h) = h").unwrap();
+	calc.clear();
+	assert!(calc.globals().is_empty());
+	assert!(calc.functions().is_empty());
+}
@@ -0,0 +1,73 @@
+use shunting_yard_calculator::{Calculator, Value, WordSize};
+
+fn calculator(word_size: &str) -> Calculator {
+	let mut calc = Calculator::new();
+	calc.set_word_size(Some(word_size.parse().unwrap()));
+	calc
+}
+
+#[test]
+fn wraps_unsigned() {
+	let mut calc = calculator("u8");
+	assert_eq!(calc.calculate("200 + 100").unwrap(), Value::Integer(44));
+	assert_eq!(calc.calculate("-1").unwrap(), Value::Integer(255));
+	assert_eq!(calc.calculate("~5").unwrap(), Value::Integer(250));
+	assert_eq!(calc.calculate("1 << 8").unwrap(), Value::Integer(0));
+	assert_eq!(calc.calculate("0 - 1 >> 4").unwrap(), Value::Integer(15));
+	assert_eq!(calc.calculate("0x1FF").unwrap(), Value::Integer(255));
+	// Floats are not affected
+	assert_eq!(calc.calculate("300.5").unwrap(), Value::Float(300.5));
+}
+
+#[test]
+fn wraps_signed() {
+	let mut calc = calculator("i8");
+	assert_eq!(calc.calculate("127 + 1").unwrap(), Value::Integer(-128));
+	assert_eq!(calc.calculate("0xFF").unwrap(), Value::Integer(-1));
+	assert_eq!(calc.calculate("-128 >> 4").unwrap(), Value::Integer(-8));
+	assert_eq!(calc.calculate("16 * 16").unwrap(), Value::Integer(0));
+
+	let mut calc = calculator("i64");
+	assert_eq!(calc.calculate("(-9223372036854775807 - 1) / -1").unwrap(), Value::Integer(i64::MIN));
+	assert_eq!(calc.calculate("9223372036854775807 + 1").unwrap(), Value::Integer(i64::MIN));
+}
+
+#[test]
+fn supports_128_bits() {
+	let mut calc = calculator("u128");
+	assert_eq!(calc.calculate("-1").unwrap().to_string(), u128::MAX.to_string());
+	assert_eq!(calc.calculate("2 ** 128 + 3").unwrap(), Value::Integer(3));
+
+	let mut calc = calculator("i128");
+	assert_eq!(calc.calculate("2 ** 127").unwrap().to_string(), i128::MIN.to_string());
+}
+
+#[test]
+fn wraps_variables_and_function_results() {
+	let mut calc = Calculator::new();
+	calc.set_var(&"x".to_string(), &Value::Integer(1000));
+	calc.calculate("def twice(n) = n * 2").unwrap();
+	calc.set_word_size(Some(WordSize::new(16, false).unwrap()));
+	assert_eq!(calc.calculate("x").unwrap(), Value::Integer(1000));
+	assert_eq!(calc.calculate("twice(40000)").unwrap(), Value::Integer(14464));
+	calc.set_word_size(Some(WordSize::new(8, true).unwrap()));
+	assert_eq!(calc.calculate("x").unwrap(), Value::Integer(-24));
+	calc.set_word_size(None);
+	assert_eq!(calc.calculate("x + 1").unwrap(), Value::Integer(1001));
+}
+
+#[test]
+fn shows_bit_patterns() {
+	let word_size = WordSize::new(16, true).unwrap();
+	assert_eq!(word_size.bit_pattern(&Value::Integer(-1)).unwrap().to_string(), "65535");
+	assert_eq!(word_size.bit_pattern(&Value::Float(1.0)), None);
+	assert_eq!(word_size.to_string(), "i16");
+}
+
+#[test]
+fn parses_word_sizes() {
+	assert_eq!("u32".parse::<WordSize>(), Ok(WordSize::new(32, false).unwrap()));
+	assert!("i12".parse::<WordSize>().is_err());
+	assert!("x8".parse::<WordSize>().is_err());
+	assert!(WordSize::new(24, true).is_none());
+}